@@ -0,0 +1,23 @@
+//! Regenerates the preflop equity table shipped with the crate:
+//!
+//! ```text
+//! cargo run --release --example preflop_table [path]
+//! ```
+//!
+//! The table is written to `data/preflop_equity.bin` unless a path is given.
+
+use rust_texas_holdem::PreflopTable;
+use std::fs::File;
+use std::io::BufWriter;
+use std::time::Instant;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "data/preflop_equity.bin".to_string());
+    let start = Instant::now();
+    let table = PreflopTable::compute();
+    table.write_to(&mut BufWriter::new(File::create(&path)?))?;
+    println!("Wrote {} in {:.1?}", path, start.elapsed());
+    Ok(())
+}
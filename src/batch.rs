@@ -0,0 +1,191 @@
+use crate::dptables::{DP, SUITS};
+use crate::evaluator7::{BINARIES_BY_ID, SUITBIT_BY_ID};
+use crate::hash_table7::NOFLUSH7;
+use crate::hashtable::FLUSH;
+
+// Hands are evaluated in chunks: first the table index of every hand in the
+// chunk is computed, touching only the small SUITS and DP tables, then all of
+// the chunk's FLUSH and NOFLUSH7 lookups are made together. The lookups are
+// independent of each other, so their cache misses overlap instead of each
+// one stalling the next hand's hashing.
+
+const CHUNK: usize = 256;
+
+// 16-bit copies of the lookup tables, half the cache footprint of the
+// originals. Ranks never exceed 7462.
+static FLUSH_16: [u16; 8192] = narrow(&FLUSH);
+static NOFLUSH7_16: [u16; 49205] = narrow(&NOFLUSH7);
+
+const fn narrow<const N: usize>(table: &[i32; N]) -> [u16; N] {
+    let mut narrow = [0u16; N];
+    let mut i = 0;
+    while i < N {
+        narrow[i] = table[i] as u16;
+        i += 1;
+    }
+    narrow
+}
+
+// Marks a key as an index into FLUSH rather than NOFLUSH7.
+const FLUSH_KEY: u32 = 1 << 31;
+
+// Table index of seven distinct cards. Like `evaluate_7cards`, only the
+// flush suit's rank bits or the rank counts are built, never both.
+fn hand_key(ids: [u8; 7]) -> u32 {
+    let ids = ids.map(usize::from);
+    let suit_hash = ids.iter().map(|&id| SUITBIT_BY_ID[id]).sum::<i32>();
+    let suit = SUITS[suit_hash as usize];
+    if suit != 0 {
+        let mut suit_binary = [0; 4];
+        for id in ids {
+            suit_binary[id & 0x3] |= BINARIES_BY_ID[id];
+        }
+        return FLUSH_KEY | suit_binary[(suit - 1) as usize] as u32;
+    }
+    let mut quinary = [0u8; 13];
+    for id in ids {
+        quinary[id >> 2] += 1;
+    }
+    hash_quinary7(&quinary)
+}
+
+// `hash_quinary(quinary, 7)` for counts that can't exceed 4, which holds
+// for distinct cards.
+fn hash_quinary7(quinary: &[u8; 13]) -> u32 {
+    let mut sum = 0;
+    let mut k = 7;
+    for (i, &count) in quinary.iter().enumerate() {
+        sum += DP[count as usize][12 - i][k];
+        k -= count as usize;
+        if k == 0 {
+            break;
+        }
+    }
+    sum
+}
+
+fn lookup(key: u32) -> u16 {
+    if key & FLUSH_KEY != 0 {
+        FLUSH_16[(key & !FLUSH_KEY) as usize]
+    } else {
+        NOFLUSH7_16[key as usize]
+    }
+}
+
+/// Evaluates many 7-card hands of distinct card ids (`rank * 4 + suit`,
+/// 0..52). `ranks[i]` gets the same value `evaluate_7cards` returns for
+/// `hands[i]`.
+///
+/// # Panics
+///
+/// Panics if `hands` and `ranks` differ in length or a card id is 52 or more.
+pub fn evaluate_batch(hands: &[[u8; 7]], ranks: &mut [u16]) {
+    assert_eq!(hands.len(), ranks.len(), "need one rank slot per hand");
+    let mut keys = [0u32; CHUNK];
+    for (hands, ranks) in hands.chunks(CHUNK).zip(ranks.chunks_mut(CHUNK)) {
+        for (key, &hand) in keys.iter_mut().zip(hands) {
+            *key = hand_key(hand);
+        }
+        for (rank, &key) in ranks.iter_mut().zip(&keys) {
+            *rank = lookup(key);
+        }
+    }
+}
+
+/// Evaluates many pairs of hole cards against one board. `ranks[i]` gets the
+/// same value `evaluate_7cards` returns for `hole_cards[i]` plus `board`.
+///
+/// # Panics
+///
+/// Panics if `hole_cards` and `ranks` differ in length or a card id is 52 or
+/// more.
+pub fn evaluate_batch_on_board(board: [u8; 5], hole_cards: &[[u8; 2]], ranks: &mut [u16]) {
+    assert_eq!(
+        hole_cards.len(),
+        ranks.len(),
+        "need one rank slot per pair of hole cards"
+    );
+    let [c, d, e, f, g] = board;
+    let mut keys = [0u32; CHUNK];
+    for (pairs, ranks) in hole_cards.chunks(CHUNK).zip(ranks.chunks_mut(CHUNK)) {
+        for (key, &[a, b]) in keys.iter_mut().zip(pairs) {
+            *key = hand_key([a, b, c, d, e, f, g]);
+        }
+        for (rank, &key) in ranks.iter_mut().zip(&keys) {
+            *rank = lookup(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator7::evaluate_7cards;
+    use itertools::Itertools;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn scalar(hand: [u8; 7]) -> u16 {
+        let [a, b, c, d, e, f, g] = hand.map(i32::from);
+        evaluate_7cards(a, b, c, d, e, f, g) as u16
+    }
+
+    #[test]
+    fn batch_matches_the_scalar_evaluator() {
+        let mut rng = StdRng::seed_from_u64(16);
+        let mut deck: Vec<u8> = (0..52).collect();
+        // Not a multiple of the chunk size, so the last chunk is partial
+        let hands: Vec<[u8; 7]> = (0..3 * CHUNK + 57)
+            .map(|_| {
+                deck.shuffle(&mut rng);
+                deck[..7].try_into().unwrap()
+            })
+            .collect();
+        let mut ranks = vec![0; hands.len()];
+        evaluate_batch(&hands, &mut ranks);
+        for (&hand, &rank) in hands.iter().zip(&ranks) {
+            assert_eq!(rank, scalar(hand), "{:?}", hand);
+        }
+    }
+
+    #[test]
+    fn board_batch_matches_the_scalar_evaluator() {
+        // A rainbow board, a monotone one and a paired one
+        for board in [
+            [0, 13, 26, 39, 50],
+            [51, 47, 43, 39, 35],
+            [0, 1, 20, 21, 44],
+        ] {
+            let hole_cards: Vec<[u8; 2]> = (0..52)
+                .filter(|id| !board.contains(id))
+                .tuple_combinations()
+                .map(|(a, b)| [a, b])
+                .collect();
+            let mut ranks = vec![0; hole_cards.len()];
+            evaluate_batch_on_board(board, &hole_cards, &mut ranks);
+            for (&[a, b], &rank) in hole_cards.iter().zip(&ranks) {
+                let [c, d, e, f, g] = board;
+                assert_eq!(rank, scalar([a, b, c, d, e, f, g]), "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_batches_do_nothing() {
+        evaluate_batch(&[], &mut []);
+        evaluate_batch_on_board([0, 1, 2, 3, 4], &[], &mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "need one rank slot per hand")]
+    fn batch_needs_a_rank_per_hand() {
+        evaluate_batch(&[[0, 1, 2, 3, 4, 5, 6]], &mut [0; 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "need one rank slot per pair of hole cards")]
+    fn board_batch_needs_a_rank_per_pair() {
+        evaluate_batch_on_board([0, 1, 2, 3, 4], &[[5, 6], [7, 8]], &mut [0; 1]);
+    }
+}
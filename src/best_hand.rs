@@ -0,0 +1,183 @@
+use crate::card::{Card, CardRank};
+use crate::evaluator::evaluate;
+use crate::rank::{get_rank_category, RankCategory};
+use itertools::Itertools;
+use std::fmt;
+
+/// The five cards that make up a player's best hand, as shown at showdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestHand {
+    /// Same value `evaluate` returns for the full set of cards.
+    pub rank: i32,
+    /// The five cards in canonical order: biggest group first, then by rank,
+    /// e.g. "Kh Kd Kc 9s 9h". Straights run high to low with the wheel
+    /// written "5 4 3 2 A".
+    pub cards: [Card; 5],
+    /// The hole cards among `cards`; empty when the board plays.
+    pub hole_cards_used: Vec<Card>,
+}
+
+impl BestHand {
+    pub fn category(&self) -> RankCategory {
+        get_rank_category(self.rank)
+    }
+}
+
+impl fmt::Display for BestHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.cards
+                .iter()
+                .map(|card| card.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+/// Finds the best five of the 5 to 7 cards formed by `hole_cards` and `board`.
+/// When several five-card subsets tie, the one using the fewest hole cards is
+/// picked, so a playing board is reported as such.
+///
+/// # Panics
+///
+/// Panics if the hole cards and board together hold fewer than 5 or more
+/// than 7 cards.
+pub fn best_hand(hole_cards: &[Card], board: &[Card]) -> BestHand {
+    let cards: Vec<Card> = hole_cards.iter().chain(board.iter()).copied().collect();
+    assert!(
+        (5..=7).contains(&cards.len()),
+        "can only evaluate 5 to 7 cards, got {}",
+        cards.len()
+    );
+
+    let (rank, _, best) = cards
+        .iter()
+        .copied()
+        .combinations(5)
+        .map(|five| {
+            let rank = evaluate(&five);
+            let hole_used = five.iter().filter(|card| hole_cards.contains(card)).count();
+            (rank, hole_used, five)
+        })
+        .min_by_key(|&(rank, hole_used, _)| (rank, hole_used))
+        .expect("at least one five-card combination");
+
+    let cards = canonical_order(best, rank);
+    let hole_cards_used = cards
+        .iter()
+        .filter(|card| hole_cards.contains(card))
+        .copied()
+        .collect();
+    BestHand {
+        rank,
+        cards,
+        hole_cards_used,
+    }
+}
+
+/// The best five cards out of 5 to 7, in canonical order.
+pub fn best_five(cards: &[Card]) -> [Card; 5] {
+    best_hand(&[], cards).cards
+}
+
+fn canonical_order(five: Vec<Card>, rank: i32) -> [Card; 5] {
+    let mut cards: [Card; 5] = five.try_into().expect("five cards");
+    let mut counts = [0u8; 13];
+    for card in &cards {
+        counts[card.rank().index()] += 1;
+    }
+    cards.sort_by(|a, b| {
+        counts[b.rank().index()]
+            .cmp(&counts[a.rank().index()])
+            .then(b.rank().cmp(&a.rank()))
+            .then(b.suit().cmp(&a.suit()))
+    });
+
+    let is_straight = matches!(
+        get_rank_category(rank),
+        RankCategory::Straight | RankCategory::StraightFlush
+    );
+    if is_straight && cards[0].rank() == CardRank::Ace && cards[1].rank() == CardRank::Five {
+        cards.rotate_left(1);
+    }
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator7::evaluate_7cards;
+    use crate::notation::parse_cards;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn names(cards: &[Card]) -> String {
+        cards
+            .iter()
+            .map(|card| card.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn rank_matches_the_seven_card_evaluator() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut deck: Vec<Card> = (0..52).map(|id| Card::from_id(id).unwrap()).collect();
+        for _ in 0..2_000 {
+            deck.shuffle(&mut rng);
+            let seven = &deck[..7];
+            let ids: Vec<i32> = seven.iter().map(Card::int32).collect();
+            let expected = evaluate_7cards(ids[0], ids[1], ids[2], ids[3], ids[4], ids[5], ids[6]);
+            let best = best_hand(&seven[..2], &seven[2..]);
+            assert_eq!(best.rank, expected, "{}", names(seven));
+            assert_eq!(evaluate(&best.cards), expected, "{}", names(seven));
+        }
+    }
+
+    #[test]
+    fn full_house_puts_the_trips_first() {
+        let hole = parse_cards("9h Kd").unwrap();
+        let board = parse_cards("Kh 2c 9s Kc 4d").unwrap();
+        let best = best_hand(&hole, &board);
+        assert_eq!(best.category(), RankCategory::FullHouse);
+        assert_eq!(best.to_string(), "Kh Kd Kc 9s 9h");
+    }
+
+    #[test]
+    fn the_wheel_runs_five_to_ace() {
+        let hole = parse_cards("Ad 3c").unwrap();
+        let board = parse_cards("5h Kd 4s 2h 9c").unwrap();
+        let best = best_hand(&hole, &board);
+        assert_eq!(best.category(), RankCategory::Straight);
+        assert_eq!(best.to_string(), "5h 4s 3c 2h Ad");
+        assert_eq!(
+            names(&best_five(&parse_cards("2s 3s 4s 5s As").unwrap())),
+            "5s 4s 3s 2s As"
+        );
+    }
+
+    #[test]
+    fn counts_the_hole_cards_that_play() {
+        // A royal flush on the board plays
+        let board = parse_cards("As Ks Qs Js Ts").unwrap();
+        let best = best_hand(&parse_cards("2c 3d").unwrap(), &board);
+        assert!(best.hole_cards_used.is_empty());
+        // A board straight ties the same straight using a hole card
+        let board = parse_cards("9c 8d 7h 6s 5c").unwrap();
+        let best = best_hand(&parse_cards("9d 2h").unwrap(), &board);
+        assert!(best.hole_cards_used.is_empty());
+        // One hole card makes the higher straight
+        let best = best_hand(&parse_cards("Th 2h").unwrap(), &board);
+        assert_eq!(best.hole_cards_used, parse_cards("Th").unwrap());
+        assert_eq!(best.to_string(), "Th 9c 8d 7h 6s");
+        // Both hole cards make the flush
+        let board = parse_cards("Ah 7h 2h Kc 5d").unwrap();
+        let best = best_hand(&parse_cards("Jh 3h").unwrap(), &board);
+        assert_eq!(best.hole_cards_used.len(), 2);
+        assert_eq!(best.category(), RankCategory::Flush);
+    }
+}
@@ -0,0 +1,505 @@
+use std::fmt;
+
+// One street of no-limit betting. Amounts are whole chips, and bets and raises
+// name the player's total commitment on the street ("raise to 300"), not what
+// they add.
+//
+// A raise must add at least as much as the last full bet or raise, starting
+// from the big blind. An all-in for less is allowed but doesn't reopen the
+// action: players who already acted may then only call or fold, unless
+// several short all-ins together add up to a full raise over what they last
+// matched. The street ends once every player still in has matched the bet or
+// is all-in, or when only one player is left.
+
+/// What a player does when it's their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Fold,
+    Check,
+    /// Matches the current bet, or puts in every chip left if that's less.
+    Call,
+    /// Opens the betting to this total.
+    Bet(u32),
+    /// Raises the current bet to this total.
+    Raise(u32),
+    /// Puts in every chip left, as a call, bet or raise.
+    AllIn,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Fold => write!(f, "fold"),
+            Action::Check => write!(f, "check"),
+            Action::Call => write!(f, "call"),
+            Action::Bet(amount) => write!(f, "bet {}", amount),
+            Action::Raise(amount) => write!(f, "raise to {}", amount),
+            Action::AllIn => write!(f, "all-in"),
+        }
+    }
+}
+
+/// The actions open to the player to act. Amounts are totals on the street,
+/// except for `Call`, which holds the chips it adds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegalAction {
+    Fold,
+    Check,
+    Call(u32),
+    Bet { min: u32, max: u32 },
+    Raise { min: u32, max: u32 },
+}
+
+/// Why an action was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BettingError {
+    /// Nobody is left to act on this street.
+    RoundComplete,
+    /// There is a bet to call.
+    CannotCheck { to_call: u32 },
+    /// There is no bet to call.
+    NothingToCall,
+    /// The betting is already open; raise instead.
+    CannotBet,
+    /// There is no bet to raise, or only a short all-in since the player
+    /// last acted.
+    CannotRaise,
+    /// A bet or raise below the minimum that isn't all-in.
+    BelowMinimum { minimum: u32 },
+    /// A bet or raise to more than the player's commitment plus stack.
+    InsufficientChips { available: u32 },
+}
+
+impl fmt::Display for BettingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BettingError::RoundComplete => write!(f, "the betting round is over"),
+            BettingError::CannotCheck { to_call } => {
+                write!(f, "cannot check facing a bet, {} to call", to_call)
+            }
+            BettingError::NothingToCall => write!(f, "there is no bet to call"),
+            BettingError::CannotBet => write!(f, "the betting is already open, raise instead"),
+            BettingError::CannotRaise => write!(f, "raising is not allowed"),
+            BettingError::BelowMinimum { minimum } => {
+                write!(f, "the minimum is {} unless all-in", minimum)
+            }
+            BettingError::InsufficientChips { available } => {
+                write!(f, "only {} chips available", available)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BettingError {}
+
+/// The betting on one street, seat by seat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BettingRound {
+    stacks_: Vec<u32>,
+    committed_: Vec<u32>,
+    folded_: Vec<bool>,
+    // Commitment after each player's last action, `None` before their first
+    acted_at_: Vec<Option<u32>>,
+    current_bet_: u32,
+    // Size of the last full bet or raise
+    last_raise_: u32,
+    big_blind_: u32,
+    to_act_: Option<usize>,
+}
+
+impl BettingRound {
+    /// A street with nothing bet yet, where `stacks[seat]` is what each
+    /// player has behind and folded players take no part. Action starts at
+    /// `first_to_act`, or the next seat after it still able to act, and the
+    /// smallest bet is `big_blind`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stacks` and `folded` differ in length or `first_to_act` is
+    /// not a seat.
+    pub fn new(
+        stacks: &[u32],
+        folded: &[bool],
+        big_blind: u32,
+        first_to_act: usize,
+    ) -> BettingRound {
+        BettingRound::with_blinds(stacks, folded, &[], big_blind, first_to_act)
+    }
+
+    /// A preflop street opened by the forced bets in `blinds`, as (seat,
+    /// amount) pairs, each capped at the seat's stack. Posting isn't acting,
+    /// so the big blind may still raise when the action comes back unraised,
+    /// and the bet to call is the full blind even if its poster is all-in
+    /// for less.
+    ///
+    /// # Panics
+    ///
+    /// As [`BettingRound::new`].
+    pub fn with_blinds(
+        stacks: &[u32],
+        folded: &[bool],
+        blinds: &[(usize, u32)],
+        big_blind: u32,
+        first_to_act: usize,
+    ) -> BettingRound {
+        assert_eq!(stacks.len(), folded.len(), "one stack per seat");
+        assert!(first_to_act < stacks.len(), "no seat {}", first_to_act);
+        let mut round = BettingRound {
+            stacks_: stacks.to_vec(),
+            committed_: vec![0; stacks.len()],
+            folded_: folded.to_vec(),
+            acted_at_: vec![None; stacks.len()],
+            current_bet_: 0,
+            last_raise_: big_blind,
+            big_blind_: big_blind,
+            to_act_: None,
+        };
+        for &(seat, amount) in blinds {
+            round.commit(seat, amount.min(round.stacks_[seat]));
+            round.current_bet_ = round.current_bet_.max(amount);
+        }
+        round.to_act_ = round.next_to_act(first_to_act);
+        round
+    }
+
+    /// The seat to act, or `None` once the street is over.
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act_
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.to_act_.is_none()
+    }
+
+    /// The highest commitment on the street.
+    pub fn current_bet(&self) -> u32 {
+        self.current_bet_
+    }
+
+    /// Chips `seat` has put in on this street.
+    pub fn committed(&self, seat: usize) -> u32 {
+        self.committed_[seat]
+    }
+
+    /// Chips `seat` has behind.
+    pub fn stack(&self, seat: usize) -> u32 {
+        self.stacks_[seat]
+    }
+
+    pub fn is_folded(&self, seat: usize) -> bool {
+        self.folded_[seat]
+    }
+
+    pub fn is_all_in(&self, seat: usize) -> bool {
+        !self.folded_[seat] && self.stacks_[seat] == 0
+    }
+
+    /// Chips `seat` must add to match the current bet.
+    pub fn to_call(&self, seat: usize) -> u32 {
+        self.current_bet_ - self.committed_[seat]
+    }
+
+    /// Chips put in by everyone on this street.
+    pub fn pot(&self) -> u32 {
+        self.committed_.iter().sum()
+    }
+
+    /// Players who haven't folded.
+    pub fn players_in_hand(&self) -> usize {
+        self.folded_.iter().filter(|&&folded| !folded).count()
+    }
+
+    /// What the player to act may do; empty once the street is over.
+    pub fn legal_actions(&self) -> Vec<LegalAction> {
+        let Some(seat) = self.to_act_ else {
+            return Vec::new();
+        };
+        let mut legal = vec![LegalAction::Fold];
+        let to_call = self.to_call(seat);
+        if to_call == 0 {
+            legal.push(LegalAction::Check);
+        } else {
+            legal.push(LegalAction::Call(to_call.min(self.stacks_[seat])));
+        }
+        let max = self.committed_[seat] + self.stacks_[seat];
+        if max > self.current_bet_ && self.can_raise(seat) {
+            let min = self.min_total().min(max);
+            legal.push(if self.current_bet_ == 0 {
+                LegalAction::Bet { min, max }
+            } else {
+                LegalAction::Raise { min, max }
+            });
+        }
+        legal
+    }
+
+    /// Plays `action` for the player to act and passes the turn on. A
+    /// refused action changes nothing.
+    pub fn apply(&mut self, action: Action) -> Result<(), BettingError> {
+        let seat = self.to_act_.ok_or(BettingError::RoundComplete)?;
+        let to_call = self.to_call(seat);
+        let all_in_total = self.committed_[seat] + self.stacks_[seat];
+        match action {
+            Action::Fold => self.folded_[seat] = true,
+            Action::Check if to_call > 0 => return Err(BettingError::CannotCheck { to_call }),
+            Action::Check => {}
+            Action::Call if to_call == 0 => return Err(BettingError::NothingToCall),
+            Action::Call => self.commit(seat, to_call.min(self.stacks_[seat])),
+            Action::Bet(_) if self.current_bet_ > 0 => return Err(BettingError::CannotBet),
+            Action::Raise(_) if self.current_bet_ == 0 => return Err(BettingError::CannotRaise),
+            Action::Bet(total) | Action::Raise(total) => self.raise_to(seat, total)?,
+            Action::AllIn if all_in_total <= self.current_bet_ => {
+                self.commit(seat, self.stacks_[seat])
+            }
+            Action::AllIn => self.raise_to(seat, all_in_total)?,
+        }
+        self.acted_at_[seat] = Some(self.committed_[seat]);
+        self.to_act_ = self.next_to_act(seat + 1);
+        Ok(())
+    }
+
+    // Smallest total a bet or raise may reach, short of going all-in
+    fn min_total(&self) -> u32 {
+        if self.current_bet_ == 0 {
+            self.big_blind_
+        } else {
+            self.current_bet_ + self.last_raise_
+        }
+    }
+
+    // Whether the action is open to `seat`: someone else still has chips, and
+    // they haven't acted, only checked, or have since faced at least a full
+    // raise
+    fn can_raise(&self, seat: usize) -> bool {
+        let opposed = (0..self.stacks_.len())
+            .any(|other| other != seat && !self.folded_[other] && self.stacks_[other] > 0);
+        if !opposed {
+            return false;
+        }
+        match self.acted_at_[seat] {
+            None | Some(0) => true,
+            Some(matched) => self.current_bet_ >= matched + self.last_raise_,
+        }
+    }
+
+    fn raise_to(&mut self, seat: usize, total: u32) -> Result<(), BettingError> {
+        let available = self.committed_[seat] + self.stacks_[seat];
+        if total > available {
+            return Err(BettingError::InsufficientChips { available });
+        }
+        if available <= self.current_bet_ || !self.can_raise(seat) {
+            return Err(BettingError::CannotRaise);
+        }
+        let minimum = self.min_total();
+        if total < minimum && total != available {
+            return Err(BettingError::BelowMinimum {
+                minimum: minimum.min(available),
+            });
+        }
+        if total >= minimum {
+            self.last_raise_ = total - self.current_bet_;
+        }
+        self.current_bet_ = total;
+        self.commit(seat, total - self.committed_[seat]);
+        Ok(())
+    }
+
+    fn commit(&mut self, seat: usize, chips: u32) {
+        self.stacks_[seat] -= chips;
+        self.committed_[seat] += chips;
+    }
+
+    fn needs_to_act(&self, seat: usize) -> bool {
+        !self.folded_[seat]
+            && self.stacks_[seat] > 0
+            && (self.acted_at_[seat].is_none() || self.committed_[seat] < self.current_bet_)
+    }
+
+    // The first seat from `from` on, wrapping around, that still has to act
+    fn next_to_act(&self, from: usize) -> Option<usize> {
+        if self.players_in_hand() <= 1 {
+            return None;
+        }
+        // Betting needs two players with chips, unless one must still call
+        let mut with_chips =
+            (0..self.stacks_.len()).filter(|&seat| !self.folded_[seat] && self.stacks_[seat] > 0);
+        if let (Some(seat), None) = (with_chips.next(), with_chips.next()) {
+            if self.committed_[seat] >= self.current_bet_ {
+                return None;
+            }
+        }
+        let seats = self.stacks_.len();
+        (0..seats)
+            .map(|offset| (from + offset) % seats)
+            .find(|&seat| self.needs_to_act(seat))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A street with nobody folded and the action starting at seat 0
+    fn street(stacks: &[u32], big_blind: u32) -> BettingRound {
+        BettingRound::new(stacks, &vec![false; stacks.len()], big_blind, 0)
+    }
+
+    fn play(round: &mut BettingRound, actions: &[Action]) {
+        for &action in actions {
+            round.apply(action).unwrap();
+        }
+    }
+
+    fn raise_range(round: &BettingRound) -> Option<(u32, u32)> {
+        round
+            .legal_actions()
+            .into_iter()
+            .find_map(|legal| match legal {
+                LegalAction::Bet { min, max } | LegalAction::Raise { min, max } => Some((min, max)),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn the_minimum_raise_follows_the_last_full_raise() {
+        let mut round = street(&[1000, 1000, 1000], 10);
+        assert_eq!(raise_range(&round), Some((10, 1000)));
+        play(&mut round, &[Action::Bet(30)]);
+        assert_eq!(raise_range(&round), Some((60, 1000)));
+        play(&mut round, &[Action::Raise(100)]);
+        assert_eq!(round.to_act(), Some(2));
+        assert_eq!(raise_range(&round), Some((170, 1000)));
+        assert_eq!(
+            round.apply(Action::Raise(169)),
+            Err(BettingError::BelowMinimum { minimum: 170 })
+        );
+        play(
+            &mut round,
+            &[Action::Raise(170), Action::Call, Action::Call],
+        );
+        assert!(round.is_complete());
+        assert_eq!(round.pot(), 510);
+    }
+
+    #[test]
+    fn a_short_all_in_does_not_reopen_the_action() {
+        let mut round = street(&[1000, 1000, 130], 10);
+        play(&mut round, &[Action::Bet(100), Action::Call, Action::AllIn]);
+        assert_eq!(round.current_bet(), 130);
+        // Seat 0 already acted and faces a raise of 30, short of 100
+        assert_eq!(round.to_act(), Some(0));
+        assert_eq!(
+            round.legal_actions(),
+            vec![LegalAction::Fold, LegalAction::Call(30)]
+        );
+        assert_eq!(
+            round.apply(Action::Raise(300)),
+            Err(BettingError::CannotRaise)
+        );
+        play(&mut round, &[Action::Call, Action::Call]);
+        assert!(round.is_complete());
+        assert_eq!(round.pot(), 390);
+    }
+
+    #[test]
+    fn short_all_ins_adding_up_to_a_full_raise_reopen_the_action() {
+        // Raises of 50 and then 50 again over the 100 seat 0 bet
+        let mut round = street(&[1000, 150, 200, 1000], 10);
+        play(
+            &mut round,
+            &[Action::Bet(100), Action::AllIn, Action::AllIn, Action::Call],
+        );
+        assert_eq!(round.to_act(), Some(0));
+        assert_eq!(raise_range(&round), Some((300, 1000)));
+
+        // Raises of 50 and 30 don't add up to 100
+        let mut round = street(&[1000, 150, 180, 1000], 10);
+        play(
+            &mut round,
+            &[Action::Bet(100), Action::AllIn, Action::AllIn, Action::Call],
+        );
+        assert_eq!(round.to_act(), Some(0));
+        assert_eq!(raise_range(&round), None);
+    }
+
+    #[test]
+    fn the_big_blind_has_the_option() {
+        let blinds = [(1, 5), (2, 10)];
+        let mut round = BettingRound::with_blinds(&[1000; 3], &[false; 3], &blinds, 10, 0);
+        assert_eq!(round.current_bet(), 10);
+        play(&mut round, &[Action::Call, Action::Call]);
+        assert_eq!(round.to_act(), Some(2));
+        assert_eq!(
+            round.legal_actions(),
+            vec![
+                LegalAction::Fold,
+                LegalAction::Check,
+                LegalAction::Raise { min: 20, max: 1000 }
+            ]
+        );
+        play(&mut round, &[Action::Check]);
+        assert!(round.is_complete());
+        assert_eq!(round.pot(), 30);
+
+        // Raising instead gives the others another turn
+        let mut round = BettingRound::with_blinds(&[1000; 3], &[false; 3], &blinds, 10, 0);
+        play(&mut round, &[Action::Call, Action::Call, Action::Raise(40)]);
+        assert_eq!(round.to_act(), Some(0));
+        assert_eq!(round.to_call(0), 30);
+    }
+
+    #[test]
+    fn the_round_ends_once_everyone_left_is_all_in() {
+        let mut round = street(&[100, 200, 300], 10);
+        play(&mut round, &[Action::AllIn, Action::AllIn, Action::Call]);
+        // Seat 2 still has chips but nobody left to bet against
+        assert!(round.is_complete());
+        assert_eq!(round.stack(2), 100);
+        assert_eq!(round.pot(), 500);
+
+        // A covered player may only call or fold an all-in
+        let mut round = street(&[100, 500], 10);
+        play(&mut round, &[Action::AllIn]);
+        assert_eq!(
+            round.legal_actions(),
+            vec![LegalAction::Fold, LegalAction::Call(100)]
+        );
+        play(&mut round, &[Action::Call]);
+        assert!(round.is_complete());
+        assert!(round.legal_actions().is_empty());
+        assert_eq!(round.apply(Action::Check), Err(BettingError::RoundComplete));
+    }
+
+    #[test]
+    fn refused_actions_leave_the_round_unchanged() {
+        let mut round = street(&[1000, 250, 1000], 10);
+        play(&mut round, &[Action::Bet(50)]);
+        let before = round.clone();
+        for (action, error) in [
+            (Action::Check, BettingError::CannotCheck { to_call: 50 }),
+            (Action::Bet(200), BettingError::CannotBet),
+            (
+                Action::Raise(80),
+                BettingError::BelowMinimum { minimum: 100 },
+            ),
+            (
+                Action::Raise(300),
+                BettingError::InsufficientChips { available: 250 },
+            ),
+        ] {
+            assert_eq!(round.apply(action), Err(error), "{}", action);
+            assert_eq!(round, before, "{}", action);
+        }
+
+        let mut round = street(&[1000, 1000], 10);
+        let before = round.clone();
+        for (action, error) in [
+            (Action::Call, BettingError::NothingToCall),
+            (Action::Raise(20), BettingError::CannotRaise),
+            (Action::Bet(5), BettingError::BelowMinimum { minimum: 10 }),
+        ] {
+            assert_eq!(round.apply(action), Err(error), "{}", action);
+            assert_eq!(round, before, "{}", action);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// Forced bets and who posts them. The big blind moves one player to the left
+// every hand, and the small blind and button follow the seats it left behind
+// (the dead-button rule). So nobody skips the big blind when a player busts:
+// the small blind may be dead, or the button may sit in front of an empty seat,
+// for a hand.
+// Heads up, the button posts the small blind and acts first before the flop
+// and last after it.
+
+/// An ante every hand, on top of the blinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ante {
+    /// Every player dealt in posts this much.
+    PerPlayer(u32),
+    /// The big blind posts this much for the whole table. When they can't
+    /// cover both, the blind comes first.
+    BigBlind(u32),
+}
+
+/// The forced bets of every hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blinds {
+    pub small_blind: u32,
+    /// Also the smallest bet, and smallest raise, on every street.
+    pub big_blind: u32,
+    pub ante: Option<Ante>,
+}
+
+impl Blinds {
+    pub fn new(small_blind: u32, big_blind: u32) -> Self {
+        Blinds {
+            small_blind,
+            big_blind,
+            ante: None,
+        }
+    }
+
+    pub fn with_ante(self, ante: u32) -> Self {
+        Blinds {
+            ante: Some(Ante::PerPlayer(ante)),
+            ..self
+        }
+    }
+
+    pub fn with_big_blind_ante(self, ante: u32) -> Self {
+        Blinds {
+            ante: Some(Ante::BigBlind(ante)),
+            ..self
+        }
+    }
+}
+
+/// Seats of the button and blinds for one hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Positions {
+    /// The seat after which the deal starts; may be empty under the
+    /// dead-button rule.
+    pub button: usize,
+    /// `None` when the small blind is dead.
+    pub small_blind: Option<usize>,
+    pub big_blind: usize,
+    // Seat the small blind belonged to this hand, posted or not
+    small_blind_seat_: usize,
+    seats_: usize,
+}
+
+impl Positions {
+    /// Positions for the first hand, with the button on `button` or the next
+    /// seat in play after it. `in_play[seat]` tells whether a seat has a
+    /// player with chips. `None` with fewer than two players.
+    pub fn first_hand(in_play: &[bool], button: usize) -> Option<Positions> {
+        if in_play.iter().filter(|&&playing| playing).count() < 2 {
+            return None;
+        }
+        let button = next_in_play(in_play, button)?;
+        let small_blind = if heads_up(in_play) {
+            button
+        } else {
+            next_in_play(in_play, button + 1)?
+        };
+        Some(Positions {
+            button,
+            small_blind: Some(small_blind),
+            big_blind: next_in_play(in_play, small_blind + 1)?,
+            small_blind_seat_: small_blind,
+            seats_: in_play.len(),
+        })
+    }
+
+    /// Positions for the hand after this one, once busted players are out of
+    /// `in_play`. `None` with fewer than two players.
+    pub fn next_hand(&self, in_play: &[bool]) -> Option<Positions> {
+        if in_play.iter().filter(|&&playing| playing).count() < 2 {
+            return None;
+        }
+        let big_blind = next_in_play(in_play, self.big_blind + 1)?;
+        if heads_up(in_play) {
+            let button = next_in_play(in_play, big_blind + 1)?;
+            return Some(Positions {
+                button,
+                small_blind: Some(button),
+                big_blind,
+                small_blind_seat_: button,
+                seats_: in_play.len(),
+            });
+        }
+        Some(Positions {
+            button: self.small_blind_seat_,
+            small_blind: in_play[self.big_blind].then_some(self.big_blind),
+            big_blind,
+            small_blind_seat_: self.big_blind,
+            seats_: in_play.len(),
+        })
+    }
+
+    /// The seat after the big blind; heads up, the button.
+    pub fn first_to_act_preflop(&self) -> usize {
+        (self.big_blind + 1) % self.seats_
+    }
+
+    /// The seat after the button; heads up, the big blind.
+    pub fn first_to_act_postflop(&self) -> usize {
+        (self.button + 1) % self.seats_
+    }
+}
+
+fn heads_up(in_play: &[bool]) -> bool {
+    in_play.iter().filter(|&&playing| playing).count() == 2
+}
+
+// The first seat from `from` on, wrapping around, with a player in play
+fn next_in_play(in_play: &[bool], from: usize) -> Option<usize> {
+    let seats = in_play.len();
+    (0..seats)
+        .map(|offset| (from + offset) % seats)
+        .find(|&seat| in_play[seat])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::betting::BettingRound;
+
+    // (button, small blind, big blind)
+    fn seats(positions: &Positions) -> (usize, Option<usize>, usize) {
+        (positions.button, positions.small_blind, positions.big_blind)
+    }
+
+    // The seat a street actually starts at: empty seats take no part
+    fn first_in_play(in_play: &[bool], first_to_act: usize) -> Option<usize> {
+        let folded: Vec<bool> = in_play.iter().map(|&playing| !playing).collect();
+        BettingRound::new(&vec![1000; in_play.len()], &folded, 10, first_to_act).to_act()
+    }
+
+    #[test]
+    fn positions_move_one_seat_a_hand() {
+        let in_play = [true; 4];
+        let first = Positions::first_hand(&in_play, 0).unwrap();
+        assert_eq!(seats(&first), (0, Some(1), 2));
+        assert_eq!(first.first_to_act_preflop(), 3);
+        assert_eq!(first.first_to_act_postflop(), 1);
+        let second = first.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&second), (1, Some(2), 3));
+        let third = second.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&third), (2, Some(3), 0));
+        assert_eq!(third.first_to_act_preflop(), 1);
+    }
+
+    #[test]
+    fn the_first_button_skips_empty_seats() {
+        let in_play = [false, false, true, true, true];
+        let first = Positions::first_hand(&in_play, 0).unwrap();
+        assert_eq!(seats(&first), (2, Some(3), 4));
+        assert_eq!(Positions::first_hand(&[true, false, false], 0), None);
+    }
+
+    #[test]
+    fn a_bust_in_the_small_blind_leaves_it_dead() {
+        let mut in_play = [true; 5];
+        let first = Positions::first_hand(&in_play, 0).unwrap();
+        assert_eq!(seats(&first), (0, Some(1), 2));
+        // The big blind busts: nobody posts the small blind next hand
+        in_play[2] = false;
+        let second = first.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&second), (1, None, 3));
+        assert_eq!(second.first_to_act_preflop(), 4);
+        assert_eq!(
+            first_in_play(&in_play, second.first_to_act_postflop()),
+            Some(3)
+        );
+        // and the button then sits in front of the empty seat
+        let third = second.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&third), (2, Some(3), 4));
+        assert_eq!(
+            first_in_play(&in_play, third.first_to_act_postflop()),
+            Some(3)
+        );
+        let fourth = third.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&fourth), (3, Some(4), 0));
+    }
+
+    #[test]
+    fn a_bust_on_the_button_seat_leaves_the_button_dead() {
+        let mut in_play = [true; 5];
+        let first = Positions::first_hand(&in_play, 0).unwrap();
+        // The small blind busts and would have had the button next
+        in_play[1] = false;
+        let second = first.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&second), (1, Some(2), 3));
+        assert_eq!(
+            first_in_play(&in_play, second.first_to_act_postflop()),
+            Some(2)
+        );
+        let third = second.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&third), (2, Some(3), 4));
+        // Everyone still in paid the big blind once
+        let fourth = third.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&fourth), (3, Some(4), 0));
+        let fifth = fourth.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&fifth), (4, Some(0), 2));
+    }
+
+    #[test]
+    fn three_handed_to_heads_up() {
+        let mut in_play = [true; 3];
+        let first = Positions::first_hand(&in_play, 0).unwrap();
+        assert_eq!(seats(&first), (0, Some(1), 2));
+        // The button busts: the small blind moves on to the big blind and
+        // the last big blind takes the button and small blind
+        in_play[0] = false;
+        let second = first.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&second), (2, Some(2), 1));
+        assert_eq!(second.first_to_act_preflop(), 2);
+        assert_eq!(
+            first_in_play(&in_play, second.first_to_act_postflop()),
+            Some(1)
+        );
+        let third = second.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&third), (1, Some(1), 2));
+        // One player left ends the game
+        in_play[1] = false;
+        assert_eq!(third.next_hand(&in_play), None);
+    }
+
+    #[test]
+    fn heads_up_the_button_acts_first_before_the_flop() {
+        let in_play = [true; 2];
+        let first = Positions::first_hand(&in_play, 0).unwrap();
+        assert_eq!(seats(&first), (0, Some(0), 1));
+        assert_eq!(first.first_to_act_preflop(), 0);
+        assert_eq!(first.first_to_act_postflop(), 1);
+        let second = first.next_hand(&in_play).unwrap();
+        assert_eq!(seats(&second), (1, Some(1), 0));
+        assert_eq!(second.first_to_act_preflop(), 1);
+        assert_eq!(second.first_to_act_postflop(), 0);
+    }
+}
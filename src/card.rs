@@ -1,5 +1,8 @@
 //card.h equivalent.
+use crate::isomorphism::SuitPermutation;
 use phf::phf_map;
+use std::fmt;
+use std::str::FromStr;
 
 pub const RANK_MAP: phf::Map<char, i32> = phf_map! {
      '2' => 0 ,  '3' => 1 ,  '4'=> 2 , '5' => 3 ,
@@ -12,92 +15,310 @@ pub const SUIT_MAP: phf::Map<char, i32> = phf_map! {
       'C'  => 0    ,   'D'  => 1    ,  'H'  => 2   , 'S'  => 3,
       'c'  => 0    ,   'd'  => 1    ,  'h'  => 2   , 's'  => 3,
 };
-#[derive(Clone)]
-pub struct Card {
-    pub id_: i32,
+
+const RANK_CHARS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+const SUIT_CHARS: [char; 4] = ['c', 'd', 'h', 's'];
+
+/// Number of distinct cards in a single pack; valid card ids are `0..CARD_COUNT`.
+pub const CARD_COUNT: i32 = 52;
+
+/// Why a card name or id could not be turned into a [`Card`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardParseError {
+    /// The name was not exactly two characters long (rank then suit).
+    InvalidLength(usize),
+    /// The first character is not one of `23456789TJQKA`.
+    UnknownRank(char),
+    /// The second character is not one of `cdhs` (either case).
+    UnknownSuit(char),
+    /// The id is outside `0..52`.
+    InvalidId(i32),
 }
 
-impl Card {
-    pub fn from_id(id_: i32) -> Card {
-        Card { id_ }
+impl fmt::Display for CardParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardParseError::InvalidLength(len) => {
+                write!(f, "a card name has 2 characters, got {}", len)
+            }
+            CardParseError::UnknownRank(c) => write!(f, "unknown card rank '{}'", c),
+            CardParseError::UnknownSuit(c) => write!(f, "unknown card suit '{}'", c),
+            CardParseError::InvalidId(id) => {
+                write!(f, "card id {} is outside 0..{}", id, CARD_COUNT)
+            }
+        }
     }
+}
 
-    pub fn from_name(name: String) -> Card {
-        if name.len() < 2 {
-            todo!("Throw an exception here");
-        }
-        let result = match RANK_MAP.get(&name.chars().nth(0).unwrap()) {
-            Some(a) => a,
-            None => &499, // Just using this as an error code.
-        };
+impl std::error::Error for CardParseError {}
 
-        let result2 = match SUIT_MAP.get(&name.chars().nth(1).unwrap()) {
-            Some(a) => a,
-            None => &500,
-        };
+/// The rank of a card, ordered from deuce to ace. The discriminant matches the
+/// `id_ / 4` part of a card id and the rank index used by `RANK_MAP`. Not to
+/// be confused with [`crate::rank::Rank`], the value of a whole hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CardRank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
 
-        Card {
-            id_: result * 4 + result2,
+impl CardRank {
+    pub const ALL: [CardRank; 13] = [
+        CardRank::Two,
+        CardRank::Three,
+        CardRank::Four,
+        CardRank::Five,
+        CardRank::Six,
+        CardRank::Seven,
+        CardRank::Eight,
+        CardRank::Nine,
+        CardRank::Ten,
+        CardRank::Jack,
+        CardRank::Queen,
+        CardRank::King,
+        CardRank::Ace,
+    ];
+
+    pub const fn from_index(index: usize) -> Option<CardRank> {
+        if index < 13 {
+            Some(CardRank::ALL[index])
+        } else {
+            None
         }
     }
-    pub fn to_name(&self) -> String {
-        let rank_index = self.id_ / 4;
-        let suit_index = self.id_ % 4;
 
-        let rank_char = match RANK_MAP.entries().find(|&(_, &v)| v == rank_index) {
-            Some((&k, _)) => k,
-            None => todo!("Handle invalid rank index here"),
-        };
+    pub const fn index(self) -> usize {
+        self as usize
+    }
 
-        let suit_char = match SUIT_MAP.entries().find(|&(_, &v)| v == suit_index) {
-            Some((&k, _)) => k,
-            None => todo!("Handle invalid suit index here"),
-        };
+    pub const fn to_char(self) -> char {
+        RANK_CHARS[self as usize]
+    }
 
-        format!("{}{}", rank_char, suit_char)
+    pub fn from_char(c: char) -> Option<CardRank> {
+        RANK_MAP
+            .get(&c.to_ascii_uppercase())
+            .map(|&index| CardRank::ALL[index as usize])
     }
-    pub fn to_clean_name(&self) -> String {
-        let name: String = self.to_name();
-        let mut p1: String = String::new();
-        let mut p2: String = String::new();
 
-        if let Some(c) = name.chars().nth(0) {
-            p1.push(c);
+    /// Long name as printed by [`Card::to_clean_name`], e.g. "Queen" or "10".
+    pub const fn clean_name(self) -> &'static str {
+        match self {
+            CardRank::Two => "2",
+            CardRank::Three => "3",
+            CardRank::Four => "4",
+            CardRank::Five => "5",
+            CardRank::Six => "6",
+            CardRank::Seven => "7",
+            CardRank::Eight => "8",
+            CardRank::Nine => "9",
+            CardRank::Ten => "10",
+            CardRank::Jack => "Jack",
+            CardRank::Queen => "Queen",
+            CardRank::King => "King",
+            CardRank::Ace => "Ace",
         }
+    }
+}
 
-        if let Some(c) = name.chars().nth(1) {
-            p2.push(c);
+impl fmt::Display for CardRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// The suit of a card. The discriminant matches the `id_ % 4` part of a card id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+    pub const fn from_index(index: usize) -> Option<Suit> {
+        if index < 4 {
+            Some(Suit::ALL[index])
+        } else {
+            None
+        }
+    }
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn to_char(self) -> char {
+        SUIT_CHARS[self as usize]
+    }
+
+    pub fn from_char(c: char) -> Option<Suit> {
+        SUIT_MAP.get(&c).map(|&index| Suit::ALL[index as usize])
+    }
+
+    pub const fn clean_name(self) -> &'static str {
+        match self {
+            Suit::Clubs => "Clubs",
+            Suit::Diamonds => "Diamonds",
+            Suit::Hearts => "Hearts",
+            Suit::Spades => "Spades",
         }
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
 
-        if p1 == "T" {
-            p1 = "10".to_string();
-        } else if p1 == "J" {
-            p1 = "Jack".to_string();
-        } else if p1 == "Q" {
-            p1 = "Queen".to_string();
-        } else if p1 == "K" {
-            p1 = "King".to_string();
-        } else if p1 == "A" {
-            p1 = "Ace".to_string();
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card {
+    id_: i32,
+}
+
+impl Card {
+    pub const fn new(rank: CardRank, suit: Suit) -> Card {
+        Card {
+            id_: rank as i32 * 4 + suit as i32,
         }
+    }
 
-        if p2 == "c" || p2 == "C" {
-            p2 = "Clubs".to_string();
-        } else if p2 == "d" || p2 == "D" {
-            p2 = "Diamonds".to_string();
-        } else if p2 == "h" || p2 == "H" {
-            p2 = "Hearts".to_string();
-        } else if p2 == "s" || p2 == "S" {
-            p2 = "Spades".to_string();
+    pub fn from_id(id_: i32) -> Result<Card, CardParseError> {
+        if !(0..CARD_COUNT).contains(&id_) {
+            return Err(CardParseError::InvalidId(id_));
         }
+        Ok(Card { id_ })
+    }
+
+    pub fn from_name(name: &str) -> Result<Card, CardParseError> {
+        let mut chars = name.chars();
+        let (rank_char, suit_char) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(r), Some(s), None) => (r, s),
+            _ => return Err(CardParseError::InvalidLength(name.chars().count())),
+        };
+
+        let rank = CardRank::from_char(rank_char).ok_or(CardParseError::UnknownRank(rank_char))?;
+        let suit = Suit::from_char(suit_char).ok_or(CardParseError::UnknownSuit(suit_char))?;
+
+        Ok(Card::new(rank, suit))
+    }
+
+    pub const fn rank(&self) -> CardRank {
+        CardRank::ALL[(self.id_ / 4) as usize]
+    }
+
+    pub const fn suit(&self) -> Suit {
+        Suit::ALL[(self.id_ % 4) as usize]
+    }
+
+    pub fn to_name(&self) -> String {
+        format!("{}{}", self.rank(), self.suit())
+    }
+    pub fn to_clean_name(&self) -> String {
+        format!(
+            "{} of {}",
+            self.rank().clean_name(),
+            self.suit().clean_name()
+        )
+    }
 
-        p1 + " of " + &p2
+    /// The same rank in the suit `permutation` sends this card's suit to.
+    pub const fn relabel_suits(&self, permutation: &SuitPermutation) -> Card {
+        Card::new(self.rank(), permutation.apply(self.suit()))
     }
 
-    pub fn int(&self) -> usize {
+    pub const fn int(&self) -> usize {
         self.id_ as usize
     }
-    pub fn int32(&self) -> i32 {
-        self.id_ as i32
+    pub const fn int32(&self) -> i32 {
+        self.id_
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank(), self.suit())
+    }
+}
+
+impl FromStr for Card {
+    type Err = CardParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Card::from_name(s)
+    }
+}
+
+impl TryFrom<&str> for Card {
+    type Error = CardParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Card::from_name(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_card_round_trips_through_its_name() {
+        for id in 0..CARD_COUNT {
+            let card = Card::from_id(id).unwrap();
+            assert_eq!(card.int32(), id);
+            assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+        }
+        let card: Card = "Td".parse().unwrap();
+        assert_eq!(card, Card::new(CardRank::Ten, Suit::Diamonds));
+        assert_eq!(card.to_string(), "Td");
+        assert_eq!(card.to_clean_name(), "10 of Diamonds");
+        // Either case reads
+        assert_eq!(Card::from_name("tD"), Ok(card));
+        assert_eq!(Card::try_from("Td"), Ok(card));
+    }
+
+    #[test]
+    fn bad_names_say_what_is_wrong() {
+        assert_eq!(Card::from_name(""), Err(CardParseError::InvalidLength(0)));
+        assert_eq!(Card::from_name("A"), Err(CardParseError::InvalidLength(1)));
+        assert_eq!(
+            Card::from_name("10h"),
+            Err(CardParseError::InvalidLength(3))
+        );
+        assert_eq!(Card::from_name("1h"), Err(CardParseError::UnknownRank('1')));
+        assert_eq!(Card::from_name("Ax"), Err(CardParseError::UnknownSuit('x')));
+        // The rank is checked first
+        assert_eq!(Card::from_name("Zz"), Err(CardParseError::UnknownRank('Z')));
+        assert_eq!(
+            "Ax".parse::<Card>().unwrap_err().to_string(),
+            "unknown card suit 'x'"
+        );
+    }
+
+    #[test]
+    fn ids_outside_the_pack_are_refused() {
+        assert_eq!(Card::from_id(-1), Err(CardParseError::InvalidId(-1)));
+        assert_eq!(Card::from_id(52), Err(CardParseError::InvalidId(52)));
+        assert_eq!(
+            CardParseError::InvalidId(52).to_string(),
+            "card id 52 is outside 0..52"
+        );
+        assert_eq!(Card::from_id(51).unwrap().to_string(), "As");
     }
 }
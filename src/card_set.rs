@@ -0,0 +1,306 @@
+use crate::card::{Card, CARD_COUNT};
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign};
+
+const FULL_MASK: u64 = (1 << CARD_COUNT) - 1;
+
+/// A set of distinct cards stored as a 64-bit mask, bit `id` standing for the
+/// card with that id. Used for dead-card and board bookkeeping where the
+/// `Vec<Card>` scans would be O(n·m).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CardSet {
+    mask_: u64,
+}
+
+impl CardSet {
+    pub const fn new() -> CardSet {
+        CardSet { mask_: 0 }
+    }
+
+    /// Every card of a single pack.
+    pub const fn full() -> CardSet {
+        CardSet { mask_: FULL_MASK }
+    }
+
+    /// Builds a set from a raw mask, ignoring bits above the last card id.
+    pub const fn from_mask(mask: u64) -> CardSet {
+        CardSet {
+            mask_: mask & FULL_MASK,
+        }
+    }
+
+    pub const fn mask(&self) -> u64 {
+        self.mask_
+    }
+
+    /// Adds a card, returning `false` if it was already present.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let bit = 1u64 << card.int();
+        let added = self.mask_ & bit == 0;
+        self.mask_ |= bit;
+        added
+    }
+
+    /// Removes a card, returning `false` if it was not present.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let bit = 1u64 << card.int();
+        let removed = self.mask_ & bit != 0;
+        self.mask_ &= !bit;
+        removed
+    }
+
+    pub const fn contains(&self, card: Card) -> bool {
+        self.mask_ & (1u64 << card.int()) != 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.mask_.count_ones() as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.mask_ == 0
+    }
+
+    pub const fn union(&self, other: CardSet) -> CardSet {
+        CardSet {
+            mask_: self.mask_ | other.mask_,
+        }
+    }
+
+    pub const fn intersection(&self, other: CardSet) -> CardSet {
+        CardSet {
+            mask_: self.mask_ & other.mask_,
+        }
+    }
+
+    pub const fn difference(&self, other: CardSet) -> CardSet {
+        CardSet {
+            mask_: self.mask_ & !other.mask_,
+        }
+    }
+
+    /// The cards of a single pack that are not in this set.
+    pub const fn complement(&self) -> CardSet {
+        CardSet {
+            mask_: !self.mask_ & FULL_MASK,
+        }
+    }
+
+    pub const fn is_disjoint(&self, other: CardSet) -> bool {
+        self.mask_ & other.mask_ == 0
+    }
+
+    pub const fn is_subset(&self, other: CardSet) -> bool {
+        self.mask_ & !other.mask_ == 0
+    }
+
+    /// Iterates the cards in ascending id order.
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter { mask_: self.mask_ }
+    }
+}
+
+pub struct CardSetIter {
+    mask_: u64,
+}
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.mask_ == 0 {
+            return None;
+        }
+        let id = self.mask_.trailing_zeros();
+        self.mask_ &= self.mask_ - 1;
+        Card::from_id(id as i32).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask_.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set = CardSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a Card>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> Self {
+        CardSet {
+            mask_: 1u64 << card.int(),
+        }
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: CardSet) -> CardSet {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, rhs: CardSet) {
+        self.mask_ |= rhs.mask_;
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: CardSet) -> CardSet {
+        self.intersection(rhs)
+    }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, rhs: CardSet) {
+        self.mask_ &= rhs.mask_;
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: CardSet) -> CardSet {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign for CardSet {
+    fn sub_assign(&mut self, rhs: CardSet) {
+        self.mask_ &= !rhs.mask_;
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        self.complement()
+    }
+}
+
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, card) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    fn set(cards: &str) -> CardSet {
+        parse_cards(cards).unwrap().iter().collect()
+    }
+
+    #[test]
+    fn insert_remove_and_contains() {
+        let ace: Card = "Ah".parse().unwrap();
+        let mut cards = CardSet::new();
+        assert!(cards.is_empty());
+        assert!(cards.insert(ace));
+        assert!(!cards.insert(ace));
+        assert!(cards.contains(ace));
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards, CardSet::from(ace));
+        assert!(cards.remove(ace));
+        assert!(!cards.remove(ace));
+        assert!(!cards.contains(ace));
+        assert!(cards.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set("Ah Kh Qh 2c");
+        let b = set("Qh 2c 7d");
+        assert_eq!(a.union(b), set("Ah Kh Qh 2c 7d"));
+        assert_eq!(a.intersection(b), set("Qh 2c"));
+        assert_eq!(a.difference(b), set("Ah Kh"));
+        assert_eq!(a | b, a.union(b));
+        assert_eq!(a & b, a.intersection(b));
+        assert_eq!(a - b, a.difference(b));
+        let mut c = a;
+        c -= b;
+        c |= set("7d");
+        c &= set("Ah 7d 9s");
+        assert_eq!(c, set("Ah 7d"));
+
+        assert!(set("Ah Kh").is_subset(a));
+        assert!(!b.is_subset(a));
+        assert!(set("7d 9s").is_disjoint(a));
+        assert!(!a.is_disjoint(b));
+    }
+
+    #[test]
+    fn the_complement_stays_within_one_pack() {
+        let full = CardSet::full();
+        assert_eq!(full.len(), 52);
+        assert_eq!(!CardSet::new(), full);
+        assert!((!full).is_empty());
+        let board = set("Ah Kd 7c");
+        assert_eq!(board.complement().len(), 49);
+        assert!(board.complement().is_disjoint(board));
+        assert_eq!(CardSet::from_mask(u64::MAX), full);
+    }
+
+    #[test]
+    fn iterates_in_ascending_id_order() {
+        let cards = set("As 2c Td 2d Kh");
+        let ids: Vec<usize> = cards.iter().map(|card| card.int()).collect();
+        assert_eq!(ids, vec![0, 1, 33, 46, 51]);
+        assert_eq!(cards.iter().len(), 5);
+        assert_eq!(cards.to_string(), "2c 2d Td Kh As");
+        assert_eq!(cards.into_iter().collect::<CardSet>(), cards);
+        assert_eq!(CardSet::full().iter().count(), 52);
+    }
+}
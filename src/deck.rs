@@ -1,6 +1,7 @@
 use rand::seq::SliceRandom;
+use rand::Rng;
 
-use crate::card::{Card, RANK_MAP, SUIT_MAP};
+use crate::card::{Card, CARD_COUNT};
 
 pub struct Deck {
     pub cards: Vec<Card>,
@@ -9,20 +10,18 @@ pub struct Deck {
 
 impl Deck {
     pub fn shuffle_deck(&mut self) {
-        let mut rng = rand::thread_rng();
-        self.cards.shuffle(&mut rng);
+        self.shuffle_with(&mut rand::thread_rng());
     }
+    /// Shuffles with a caller-supplied generator, so a seeded `rng` always
+    /// gives the same order.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+    /// A shoe of `pack_count` full packs, unshuffled.
     pub fn new(pack_count: u8) -> Deck {
-        let mut cards = Vec::new();
-        RANK_MAP.into_iter().for_each(|rank| {
-            SUIT_MAP.into_iter().for_each(|suit| {
-                let new_card = rank.0.to_string() + &suit.0.to_string();
-                cards.push(Card::from_name(new_card));
-            });
-        });
-        Deck {
-            cards: cards,
-            pack_count: pack_count,
-        }
+        let cards = (0..pack_count)
+            .flat_map(|_| (0..CARD_COUNT).filter_map(|id| Card::from_id(id).ok()))
+            .collect();
+        Deck { cards, pack_count }
     }
 }
@@ -0,0 +1,324 @@
+use crate::card::{Card, CardRank, Suit};
+use crate::card_set::CardSet;
+use std::fmt;
+
+// Draws are read off the flop or turn from suit and rank counts alone. A draw
+// has to use at least one hole card: four hearts on the board are nobody's
+// flush draw in particular. Straights are found on a 14-bit rank mask where
+// bit 0 is the ace playing low and bits 1 to 13 are Two to Ace.
+
+/// The kinds of draw `find_draws` reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DrawKind {
+    /// Four cards to a flush.
+    FlushDraw,
+    /// Four cards to a flush, holding the highest card of the suit left.
+    NutFlushDraw,
+    /// Four ranks in a row that either end completes.
+    OpenEndedStraightDraw,
+    /// One rank completes a straight.
+    Gutshot,
+    /// Two ranks each fill a different gap.
+    DoubleGutshot,
+    /// Three cards to a flush on the flop, needing the turn and river.
+    BackdoorFlushDraw,
+    /// Three cards to a straight on the flop, needing the turn and river.
+    BackdoorStraightDraw,
+}
+
+impl DrawKind {
+    pub const fn is_flush_draw(self) -> bool {
+        matches!(self, DrawKind::FlushDraw | DrawKind::NutFlushDraw)
+    }
+
+    pub const fn is_straight_draw(self) -> bool {
+        matches!(
+            self,
+            DrawKind::OpenEndedStraightDraw | DrawKind::Gutshot | DrawKind::DoubleGutshot
+        )
+    }
+}
+
+impl fmt::Display for DrawKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DrawKind::FlushDraw => "flush draw",
+            DrawKind::NutFlushDraw => "nut flush draw",
+            DrawKind::OpenEndedStraightDraw => "open-ended straight draw",
+            DrawKind::Gutshot => "gutshot",
+            DrawKind::DoubleGutshot => "double gutshot",
+            DrawKind::BackdoorFlushDraw => "backdoor flush draw",
+            DrawKind::BackdoorStraightDraw => "backdoor straight draw",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One draw and the unseen cards that complete it on the next street.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub kind: DrawKind,
+    /// Highest card first; empty for backdoor draws, which need two cards.
+    pub outs: Vec<Card>,
+}
+
+/// Every draw of a hand.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Draws {
+    pub draws: Vec<Draw>,
+}
+
+impl Draws {
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    pub fn contains(&self, kind: DrawKind) -> bool {
+        self.draws.iter().any(|draw| draw.kind == kind)
+    }
+
+    /// A flush draw together with a straight draw.
+    pub fn is_combo_draw(&self) -> bool {
+        self.draws.iter().any(|draw| draw.kind.is_flush_draw())
+            && self.draws.iter().any(|draw| draw.kind.is_straight_draw())
+    }
+
+    /// Cards completing any draw, each once, highest first.
+    pub fn outs(&self) -> Vec<Card> {
+        let outs: CardSet = self.draws.iter().flat_map(|draw| &draw.outs).collect();
+        let mut outs: Vec<Card> = outs.iter().collect();
+        outs.reverse();
+        outs
+    }
+}
+
+/// Finds the draws `hole_cards` have on a flop or turn `board`. There are
+/// none before the flop or on the river.
+pub fn find_draws(hole_cards: &[Card], board: &[Card]) -> Draws {
+    let mut draws = Draws::default();
+    if !(3..=4).contains(&board.len()) {
+        return draws;
+    }
+    let known: CardSet = hole_cards.iter().chain(board).collect();
+    let unseen = known.complement();
+    let flop = board.len() == 3;
+
+    for suit in Suit::ALL {
+        let in_suit = known.iter().filter(|card| card.suit() == suit).count();
+        let ours = hole_cards.iter().filter(|card| card.suit() == suit);
+        let Some(best_held) = ours.map(|card| card.rank()).max() else {
+            continue;
+        };
+        if in_suit == 4 {
+            // Nut when no unseen card of the suit ranks higher
+            let nut = !unseen
+                .iter()
+                .any(|card| card.suit() == suit && card.rank() > best_held);
+            draws.draws.push(Draw {
+                kind: if nut {
+                    DrawKind::NutFlushDraw
+                } else {
+                    DrawKind::FlushDraw
+                },
+                outs: cards_where(unseen, |card| card.suit() == suit),
+            });
+        } else if in_suit == 3 && flop {
+            draws.draws.push(Draw {
+                kind: DrawKind::BackdoorFlushDraw,
+                outs: Vec::new(),
+            });
+        }
+    }
+
+    let ranks = rank_mask(known.iter());
+    let board_ranks = rank_mask(board.iter().copied());
+    // Ranks only a hole card brings
+    let ours = rank_mask(hole_cards.iter().copied()) & !board_ranks;
+    let ours_straight = |mask: u16| {
+        STRAIGHTS
+            .iter()
+            .any(|&straight| mask & straight == straight && straight & ours != 0)
+    };
+    if ours_straight(ranks) {
+        return draws;
+    }
+
+    let completing: Vec<CardRank> = CardRank::ALL
+        .into_iter()
+        .filter(|&rank| ranks & rank_bits(rank) == 0 && ours_straight(ranks | rank_bits(rank)))
+        .collect();
+    let straight_outs =
+        |completing: &[CardRank]| cards_where(unseen, |card| completing.contains(&card.rank()));
+    match completing[..] {
+        [] => {}
+        [_] => draws.draws.push(Draw {
+            kind: DrawKind::Gutshot,
+            outs: straight_outs(&completing),
+        }),
+        _ => {
+            // Open-ended when four ranks in a row are held and the ranks on
+            // both sides complete
+            let completing_mask = completing
+                .iter()
+                .fold(0, |mask, &rank| mask | rank_bits(rank));
+            let open_ended = STRAIGHTS.windows(2).any(|pair| {
+                let (lower, upper) = (pair[0], pair[1]);
+                let run = lower & upper;
+                let ends = lower ^ upper;
+                ranks & run == run && completing_mask & ends == ends
+            });
+            draws.draws.push(Draw {
+                kind: if open_ended {
+                    DrawKind::OpenEndedStraightDraw
+                } else {
+                    DrawKind::DoubleGutshot
+                },
+                outs: straight_outs(&completing),
+            });
+        }
+    }
+
+    if flop && completing.is_empty() {
+        // Three ranks of some straight, one of them ours
+        let backdoor = STRAIGHTS
+            .iter()
+            .any(|&straight| (ranks & straight).count_ones() == 3 && straight & ours != 0);
+        if backdoor {
+            draws.draws.push(Draw {
+                kind: DrawKind::BackdoorStraightDraw,
+                outs: Vec::new(),
+            });
+        }
+    }
+    draws
+}
+
+// The ten straights as 14-bit rank masks, wheel first
+const STRAIGHTS: [u16; 10] = [
+    0x1f,
+    0x1f << 1,
+    0x1f << 2,
+    0x1f << 3,
+    0x1f << 4,
+    0x1f << 5,
+    0x1f << 6,
+    0x1f << 7,
+    0x1f << 8,
+    0x1f << 9,
+];
+
+// Bit for a rank, plus bit 0 for the ace playing low
+fn rank_bits(rank: CardRank) -> u16 {
+    let bit = 1 << (rank.index() + 1);
+    if rank == CardRank::Ace {
+        bit | 1
+    } else {
+        bit
+    }
+}
+
+fn rank_mask(cards: impl Iterator<Item = Card>) -> u16 {
+    cards.fold(0, |mask, card| mask | rank_bits(card.rank()))
+}
+
+fn cards_where(cards: CardSet, keep: impl Fn(&Card) -> bool) -> Vec<Card> {
+    let mut kept: Vec<Card> = cards.iter().filter(keep).collect();
+    kept.reverse();
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    fn draws(hole: &str, board: &str) -> Draws {
+        find_draws(&parse_cards(hole).unwrap(), &parse_cards(board).unwrap())
+    }
+
+    fn kinds(draws: &Draws) -> Vec<DrawKind> {
+        draws.draws.iter().map(|draw| draw.kind).collect()
+    }
+
+    #[test]
+    fn flush_draws() {
+        let nut = draws("Ah Kh", "Qh 7h 2c");
+        assert_eq!(
+            kinds(&nut),
+            vec![DrawKind::NutFlushDraw, DrawKind::BackdoorStraightDraw]
+        );
+        assert_eq!(
+            nut.draws[0].outs,
+            parse_cards("Jh Th 9h 8h 6h 5h 4h 3h 2h").unwrap()
+        );
+        // The ace of hearts is still out there
+        let second = draws("Kh 9h", "Qh 7h 2c");
+        assert_eq!(
+            kinds(&second),
+            vec![DrawKind::FlushDraw, DrawKind::BackdoorStraightDraw]
+        );
+        assert_eq!(second.outs().len(), 9);
+        // Four of a suit on the board is nobody's draw
+        assert!(draws("Ac Kd", "Qh 7h 2h 9h").is_empty());
+    }
+
+    #[test]
+    fn straight_draws() {
+        let open_ended = draws("9c 8d", "7h 6s 2c");
+        assert_eq!(kinds(&open_ended), vec![DrawKind::OpenEndedStraightDraw]);
+        assert_eq!(
+            open_ended.outs(),
+            parse_cards("Ts Th Td Tc 5s 5h 5d 5c").unwrap()
+        );
+
+        let gutshot = draws("9c 8d", "Jh 7s 2c");
+        assert_eq!(kinds(&gutshot), vec![DrawKind::Gutshot]);
+        assert_eq!(gutshot.outs(), parse_cards("Ts Th Td Tc").unwrap());
+
+        let double = draws("9c 5d", "7h 6s 3c");
+        assert_eq!(kinds(&double), vec![DrawKind::DoubleGutshot]);
+        assert_eq!(
+            double.outs(),
+            parse_cards("8s 8h 8d 8c 4s 4h 4d 4c").unwrap()
+        );
+
+        // The ace plays low for the wheel
+        let wheel = draws("Ad 4c", "3h 2s Kd");
+        assert_eq!(kinds(&wheel), vec![DrawKind::Gutshot]);
+        assert_eq!(wheel.outs().len(), 4);
+    }
+
+    #[test]
+    fn a_combo_draw_counts_shared_outs_once() {
+        let combo = draws("9h 8h", "7h 6h 2c");
+        assert_eq!(
+            kinds(&combo),
+            vec![DrawKind::FlushDraw, DrawKind::OpenEndedStraightDraw]
+        );
+        assert!(combo.is_combo_draw());
+        // 9 hearts and 8 straight cards, two of them hearts
+        assert_eq!(combo.outs().len(), 15);
+    }
+
+    #[test]
+    fn backdoor_draws_only_on_the_flop() {
+        let flop = draws("Ah 5h", "Kh 9c 2d");
+        assert!(flop.contains(DrawKind::BackdoorFlushDraw));
+        assert!(flop.draws.iter().all(|draw| draw.outs.is_empty()));
+        assert!(!draws("Ah 5h", "Kh 9c 2d Js").contains(DrawKind::BackdoorFlushDraw));
+    }
+
+    #[test]
+    fn no_draws_without_a_hole_card_or_once_made() {
+        // The board's own straight draw isn't ours
+        assert!(draws("2c 2d", "9h 8s 7c Kd").is_empty());
+        // A made straight has no straight draw left
+        assert!(!draws("9c 8d", "7h 6s 5c")
+            .draws
+            .iter()
+            .any(|draw| draw.kind.is_straight_draw()));
+        // Nor is there any before the flop or on the river
+        assert!(draws("9c 8d", "").is_empty());
+        assert!(draws("9c 8d", "7h 6s 2c Kd Qh").is_empty());
+    }
+}
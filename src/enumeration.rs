@@ -0,0 +1,290 @@
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::evaluator::evaluate_hand;
+use crate::evaluator7::evaluate_7cards;
+use crate::hand::Hand;
+use crate::multideck::seven_card_evaluator;
+use crate::probability::{record_showdown, EquityResult, RangeEquityError, Tally};
+use crate::range::Range;
+use crate::rank::{get_rank_category, RankCategory};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+// Exact counterparts of the Monte Carlo functions in `probability.rs`. They
+// walk every remaining board (and, for win probability, every set of opponent
+// holdings) so the result is deterministic. `probability.rs` switches to them
+// when `hand_enumeration_count`/`win_enumeration_count` are small enough.
+
+/// Number of ways to choose `k` items out of `n`, saturating at `u64::MAX`.
+pub fn choose(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        if result > u64::MAX as u128 {
+            return u64::MAX;
+        }
+    }
+    result as u64
+}
+
+/// Number of boards `enumerate_hand_probabilities` evaluates.
+pub fn hand_enumeration_count(community_len: usize, deck_len: usize) -> u64 {
+    choose(deck_len, 5usize.saturating_sub(community_len))
+}
+
+/// Number of (board, opponent holdings) showdowns `enumerate_win_probability`
+/// evaluates. Opponent holdings are counted as unordered sets of hands.
+pub fn win_enumeration_count(community_len: usize, deck_len: usize, opponents: usize) -> u64 {
+    let missing = 5usize.saturating_sub(community_len);
+    let mut count = choose(deck_len, missing) as u128;
+    let mut left = deck_len.saturating_sub(missing);
+    for i in 0..opponents {
+        count = count * choose(left, 2) as u128 / (i + 1) as u128;
+        if count > u64::MAX as u128 {
+            return u64::MAX;
+        }
+        left = left.saturating_sub(2);
+    }
+    count as u64
+}
+
+// Calls `visit` with the 5 community cards of every possible runout.
+fn for_each_board<F: FnMut(&[i32; 5], &[bool])>(community: &[Card], deck: &[Card], mut visit: F) {
+    let missing = 5 - community.len();
+    let mut board = [0i32; 5];
+    for (slot, card) in board.iter_mut().zip(community) {
+        *slot = card.int32();
+    }
+    let mut used = vec![false; deck.len()];
+    for indices in (0..deck.len()).combinations(missing) {
+        for (slot, &index) in indices.iter().enumerate() {
+            board[community.len() + slot] = deck[index].int32();
+            used[index] = true;
+        }
+        visit(&board, &used);
+        for &index in &indices {
+            used[index] = false;
+        }
+    }
+}
+
+type Evaluate7 = fn(i32, i32, i32, i32, i32, i32, i32) -> i32;
+
+fn rank_7(evaluate: Evaluate7, hole: [i32; 2], board: &[i32; 5]) -> i32 {
+    evaluate(
+        hole[0], hole[1], board[0], board[1], board[2], board[3], board[4],
+    )
+}
+
+/// Exact probability (in percent) of finishing with each hand category.
+/// `deck` may hold several copies of a card when `pack_count` is above 1.
+pub fn enumerate_hand_probabilities(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    deck: &[Card],
+    pack_count: usize,
+) -> HashMap<RankCategory, f64> {
+    let evaluate = seven_card_evaluator(pack_count);
+    let hole = [hole_cards[0].int32(), hole_cards[1].int32()];
+    let mut counts: HashMap<RankCategory, f64> = HashMap::new();
+    let mut total = 0.0;
+    for_each_board(community_cards, deck, |board, _| {
+        let category = get_rank_category(rank_7(evaluate, hole, board));
+        *counts.entry(category).or_insert(0.0) += 1.0;
+        total += 1.0;
+    });
+
+    for count in counts.values_mut() {
+        *count = *count / total * 100.0;
+    }
+    counts
+}
+
+/// Exact win/tie/lose split and pot equity against `num_players - 1`
+/// opponents dealt any two of the remaining cards.
+pub fn enumerate_win_probability(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    num_players: usize,
+    deck: &[Card],
+    pack_count: usize,
+) -> EquityResult {
+    let evaluate = seven_card_evaluator(pack_count);
+    let hole = [hole_cards[0].int32(), hole_cards[1].int32()];
+    let opponents = num_players.saturating_sub(1);
+    let mut tally = Tally::default();
+    let mut free: Vec<usize> = Vec::with_capacity(deck.len());
+    let mut pair_ranks: Vec<i32> = Vec::new();
+
+    for_each_board(community_cards, deck, |board, used| {
+        let our_rank = rank_7(evaluate, hole, board);
+
+        // Rank every pair of cards left after this board once, then count the
+        // outcome of every set of disjoint pairs.
+        free.clear();
+        free.extend((0..deck.len()).filter(|&i| !used[i]));
+        let n = free.len();
+        pair_ranks.clear();
+        pair_ranks.resize(n * n, 0);
+        // With one pack the board is hashed once and each pair added to it
+        let board_hand = (pack_count == 1).then(|| {
+            let cards = board.map(|id| Card::from_id(id).expect("board holds card ids"));
+            Hand::from_cards(&cards)
+        });
+        for a in 0..n {
+            for b in a + 1..n {
+                let (first, second) = (deck[free[a]], deck[free[b]]);
+                pair_ranks[a * n + b] = match &board_hand {
+                    Some(board_hand) => {
+                        evaluate_hand(&board_hand.with_hole_cards(first, second)).value()
+                    }
+                    None => rank_7(evaluate, [first.int32(), second.int32()], board),
+                };
+            }
+        }
+
+        let mut taken = vec![false; n];
+        let showdown = Showdown {
+            pair_ranks: &pair_ranks,
+            n,
+            our_rank,
+        };
+        showdown.count_opponent_sets(opponents, 0, false, 0, &mut taken, &mut tally);
+    });
+
+    tally.result()
+}
+
+// The ranks of every two-card holding left after one board.
+struct Showdown<'a> {
+    pair_ranks: &'a [i32],
+    n: usize,
+    our_rank: i32,
+}
+
+impl Showdown<'_> {
+    // Walks every unordered set of `opponents` disjoint pairs. Pairs are
+    // chosen by increasing first card so each set is seen once.
+    fn count_opponent_sets(
+        &self,
+        opponents: usize,
+        first: usize,
+        beaten: bool,
+        tied_with: usize,
+        taken: &mut [bool],
+        tally: &mut Tally,
+    ) {
+        if opponents == 0 {
+            tally.record(beaten, tied_with);
+            return;
+        }
+        for a in first..self.n {
+            if taken[a] {
+                continue;
+            }
+            taken[a] = true;
+            for b in a + 1..self.n {
+                if taken[b] {
+                    continue;
+                }
+                taken[b] = true;
+                let rank = self.pair_ranks[a * self.n + b];
+                self.count_opponent_sets(
+                    opponents - 1,
+                    a + 1,
+                    beaten || rank < self.our_rank,
+                    tied_with + usize::from(rank == self.our_rank),
+                    taken,
+                    tally,
+                );
+                taken[b] = false;
+            }
+            taken[a] = false;
+        }
+    }
+}
+
+/// Upper bound on the (combo assignment, board) deals
+/// `enumerate_range_equity` walks; clashing combos are skipped.
+pub fn range_enumeration_count(ranges: &[Range], community_len: usize) -> u64 {
+    let missing = 5usize.saturating_sub(community_len);
+    let deck_len = 52usize.saturating_sub(community_len + 2 * ranges.len());
+    let mut count = choose(deck_len, missing) as u128;
+    for range in ranges {
+        count *= range.len() as u128;
+        if count > u64::MAX as u128 {
+            return u64::MAX;
+        }
+    }
+    count as u64
+}
+
+/// Exact range-vs-range equity: every non-clashing assignment of combos,
+/// weighted by the product of their weights, against every runout. Fails
+/// when no assignment avoids a clash.
+pub fn enumerate_range_equity(
+    ranges: &[Range],
+    community_cards: &[Card],
+) -> Result<Vec<EquityResult>, RangeEquityError> {
+    let board_cards: CardSet = community_cards.iter().collect();
+    let ranges: Vec<Range> = ranges
+        .iter()
+        .map(|range| range.without(board_cards))
+        .collect();
+    let mut tallies = vec![Tally::default(); ranges.len()];
+    let mut holdings: Vec<[Card; 2]> = Vec::with_capacity(ranges.len());
+    assign_combos(
+        &ranges,
+        community_cards,
+        board_cards,
+        1.0,
+        &mut holdings,
+        &mut tallies,
+    );
+    if tallies.first().is_some_and(|tally| tally.total() == 0.0) {
+        return Err(RangeEquityError::NoLegalDeal);
+    }
+    Ok(tallies.iter().map(Tally::result).collect())
+}
+
+// Picks a combo for each remaining range in turn, then runs every board.
+fn assign_combos(
+    ranges: &[Range],
+    community_cards: &[Card],
+    used: CardSet,
+    weight: f64,
+    holdings: &mut Vec<[Card; 2]>,
+    tallies: &mut [Tally],
+) {
+    let Some((range, rest)) = ranges.split_first() else {
+        let deck: Vec<Card> = used.complement().iter().collect();
+        let mut ranks = vec![0; holdings.len()];
+        for_each_board(community_cards, &deck, |board, _| {
+            for (rank, combo) in ranks.iter_mut().zip(holdings.iter()) {
+                *rank = rank_7(evaluate_7cards, [combo[0].int32(), combo[1].int32()], board);
+            }
+            record_showdown(&ranks, tallies, weight);
+        });
+        return;
+    };
+    for &(combo, combo_weight) in range.combos() {
+        if used.contains(combo[0]) || used.contains(combo[1]) {
+            continue;
+        }
+        holdings.push(combo);
+        let used = used | CardSet::from_iter(combo);
+        assign_combos(
+            rest,
+            community_cards,
+            used,
+            weight * combo_weight,
+            holdings,
+            tallies,
+        );
+        holdings.pop();
+    }
+}
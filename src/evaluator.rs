@@ -1,10 +1,53 @@
 use crate::card::Card;
+use crate::card_set::CardSet;
 use crate::hand::Hand;
 use crate::hash::hash_quinary;
+use crate::hash_table5::NOFLUSH5;
+use crate::hash_table6::NOFLUSH6;
 use crate::hash_table7::NOFLUSH7;
 use crate::hashtable::FLUSH;
+use crate::multideck::evaluate_multideck;
 use crate::rank::Rank;
-use crate::{dptables::SUITS, evaluator7::evaluate_7cards};
+use crate::{
+    dptables::SUITS, evaluator5::evaluate_5cards, evaluator6::evaluate_6cards,
+    evaluator7::evaluate_7cards,
+};
+
+/// Evaluates the best five-card hand out of 5, 6 or 7 cards. The result is on
+/// the same 1 (royal flush) to 7462 (7-high) scale as `evaluate_7cards`.
+/// Cards dealt from a multi-pack shoe may repeat; five of a kind then ranks
+/// -12 to 0, see `evaluate_multideck`.
+///
+/// # Panics
+///
+/// Panics if `cards` holds fewer than 5 or more than 7 cards.
+pub fn evaluate(cards: &[Card]) -> i32 {
+    if cards.iter().collect::<CardSet>().len() < cards.len() {
+        let ids: Vec<i32> = cards.iter().map(|card| card.int32()).collect();
+        return evaluate_multideck(&ids);
+    }
+    match *cards {
+        [a, b, c, d, e] => evaluate_5cards(a.int32(), b.int32(), c.int32(), d.int32(), e.int32()),
+        [a, b, c, d, e, f] => evaluate_6cards(
+            a.int32(),
+            b.int32(),
+            c.int32(),
+            d.int32(),
+            e.int32(),
+            f.int32(),
+        ),
+        [a, b, c, d, e, f, g] => evaluate_7cards(
+            a.int32(),
+            b.int32(),
+            c.int32(),
+            d.int32(),
+            e.int32(),
+            f.int32(),
+            g.int32(),
+        ),
+        _ => panic!("can only evaluate 5 to 7 cards, got {}", cards.len()),
+    }
+}
 
 pub fn evaluate_cards(
     a: &Card,
@@ -15,7 +58,7 @@ pub fn evaluate_cards(
     f: &Card,
     g: &Card,
 ) -> Rank {
-    return Rank::from_value(evaluate_7cards(
+    Rank::from_value(evaluate_7cards(
         a.int() as i32,
         b.int() as i32,
         c.int() as i32,
@@ -23,9 +66,14 @@ pub fn evaluate_cards(
         e.int() as i32,
         f.int() as i32,
         g.int() as i32,
-    ));
+    ))
 }
 
+/// Evaluates a [`Hand`] of 5, 6 or 7 cards.
+///
+/// # Panics
+///
+/// Panics if the hand holds fewer than 5 or more than 7 cards.
 pub fn evaluate_hand(hand: &Hand) -> Rank {
     if SUITS[*hand.get_suit_hash() as usize] != 0 {
         return Rank::from_value(
@@ -36,5 +84,81 @@ pub fn evaluate_hand(hand: &Hand) -> Rank {
 
     let hash: u32 = hash_quinary(hand.get_quinary(), *hand.size() as i32);
 
-    return Rank::from_value(NOFLUSH7[hash as usize]);
+    Rank::from_value(match *hand.size() {
+        5 => NOFLUSH5[hash as usize],
+        6 => NOFLUSH6[hash as usize],
+        7 => NOFLUSH7[hash as usize],
+        size => panic!("can only evaluate 5 to 7 cards, got {}", size),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rank::{get_rank_category, RankCategory};
+    use itertools::Itertools;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn pack() -> Vec<Card> {
+        CardSet::full().iter().collect()
+    }
+
+    #[test]
+    fn every_five_card_hand() {
+        // No five of a kind from a single pack
+        let mut counts = [0usize; 10];
+        let mut values = std::collections::HashSet::new();
+        for five in pack().into_iter().combinations(5) {
+            let rank = evaluate(&five);
+            counts[get_rank_category(rank) as usize] += 1;
+            values.insert(rank);
+        }
+        assert_eq!(values.len(), 7462);
+        assert_eq!(
+            RankCategory::ALL.map(|category| counts[category as usize]),
+            [0, 40, 624, 3744, 5108, 10200, 54912, 123552, 1098240, 1302540]
+        );
+    }
+
+    #[test]
+    fn five_and_six_cards_agree_with_the_seven_card_path() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut deck = pack();
+        for _ in 0..5_000 {
+            deck.shuffle(&mut rng);
+            let seven = &deck[..7];
+            let best = evaluate(seven);
+            let best_five = seven
+                .iter()
+                .copied()
+                .combinations(5)
+                .map(|five| evaluate(&five))
+                .min();
+            let best_six = seven
+                .iter()
+                .copied()
+                .combinations(6)
+                .map(|six| evaluate(&six))
+                .min();
+            assert_eq!(best_five, Some(best), "{:?}", seven);
+            assert_eq!(best_six, Some(best), "{:?}", seven);
+            // A six-card hand is its own best five out of six
+            let six = &seven[..6];
+            let six_best_five = six
+                .iter()
+                .copied()
+                .combinations(5)
+                .map(|five| evaluate(&five))
+                .min();
+            assert_eq!(six_best_five, Some(evaluate(six)), "{:?}", six);
+            for cards in [&seven[..5], six, seven] {
+                assert_eq!(
+                    evaluate_hand(&Hand::from_cards(cards)).value(),
+                    evaluate(cards)
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,36 @@
+use crate::dptables::SUITS;
+use crate::evaluator7::{BINARIES_BY_ID, SUITBIT_BY_ID};
+use crate::hash::hash_quinary;
+use crate::hash_table5::NOFLUSH5;
+use crate::hashtable::FLUSH;
+
+pub fn evaluate_5cards(a: i32, b: i32, c: i32, d: i32, e: i32) -> i32 {
+    let mut suit_hash: i32 = 0;
+    suit_hash += SUITBIT_BY_ID[a as usize];
+    suit_hash += SUITBIT_BY_ID[b as usize];
+    suit_hash += SUITBIT_BY_ID[c as usize];
+    suit_hash += SUITBIT_BY_ID[d as usize];
+    suit_hash += SUITBIT_BY_ID[e as usize];
+
+    if SUITS[suit_hash as usize] != 0 {
+        let suit_binary: i32 = BINARIES_BY_ID[a as usize]
+            | BINARIES_BY_ID[b as usize]
+            | BINARIES_BY_ID[c as usize]
+            | BINARIES_BY_ID[d as usize]
+            | BINARIES_BY_ID[e as usize];
+
+        return FLUSH[suit_binary as usize];
+    }
+
+    let mut quinary: [u8; 13] = [0; 13];
+
+    quinary[(a >> 2) as usize] += 1;
+    quinary[(b >> 2) as usize] += 1;
+    quinary[(c >> 2) as usize] += 1;
+    quinary[(d >> 2) as usize] += 1;
+    quinary[(e >> 2) as usize] += 1;
+
+    let hash: u32 = hash_quinary(&quinary, 5);
+
+    NOFLUSH5[hash as usize]
+}
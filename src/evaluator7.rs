@@ -53,5 +53,5 @@ pub fn evaluate_7cards(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32) -
 
     let hash: u32 = hash_quinary(&quinary, 7);
 
-    return NOFLUSH7[hash as usize];
+    NOFLUSH7[hash as usize]
 }
@@ -1,17 +1,28 @@
 use crate::{
+    betting::{Action, BettingRound, LegalAction},
+    blinds::{Ante, Blinds, Positions},
     card::Card,
     deck::Deck,
-    evaluator7,
-    player::{self, Decision, DecisionType, Player},
-};
-use itertools::MinMaxResult;
-use std::{
-    borrow::{Borrow, BorrowMut},
-    fmt::format,
-    io::{self, Write},
+    evaluator::evaluate,
+    player::{Decision, DecisionType, Player},
+    pot::settle,
+    rank::get_rank_category,
+    strength::ranked_holdings,
+    texture::{analyze_board, hands_that_beat},
 };
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::io::{self, Write};
 use term_size::dimensions;
 
+fn read_number() -> Option<u32> {
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+    input.trim().parse().ok()
+}
+
 fn clear_console() {
     print!("\x1B[2J\x1B[1;1H"); // ANSI escape sequence for clearing the console
     io::stdout().flush().unwrap();
@@ -37,34 +48,107 @@ pub struct Game {
     pub deck: Deck,
     pub community_cards: Vec<Card>,
     pub turn_count: u8,
-    pub pot_value: f32,
-    pub entry_cost: f32,
+    pub pot_value: u32,
+    pub blinds: Blinds,
+    /// `None` once fewer than two players have chips.
+    pub positions: Option<Positions>,
+    // Shuffles every hand after the first
+    rng_: StdRng,
 }
 impl Game {
-    pub fn new(player_count: u8, entry_cost: f32) -> Game {
+    pub fn new(player_count: u8, blinds: Blinds) -> Game {
+        Game::with_rng(player_count, blinds, &mut rand::thread_rng())
+    }
+    /// A game whose shuffle, and so every card dealt, is determined by `seed`.
+    pub fn with_seed(player_count: u8, blinds: Blinds, seed: u64) -> Game {
+        Game::with_rng(player_count, blinds, &mut StdRng::seed_from_u64(seed))
+    }
+    pub fn with_rng<R: Rng + ?Sized>(player_count: u8, blinds: Blinds, rng: &mut R) -> Game {
         let mut new_deck = Deck::new(4);
-        Deck::shuffle_deck(&mut new_deck);
+        new_deck.shuffle_with(rng);
+        let players: Vec<Player> = (0..player_count).map(|_| Player::new()).collect();
+        let in_play: Vec<bool> = players.iter().map(|pl| pl.balance > 0).collect();
         let mut game = Game {
-            player_count: player_count,
+            player_count,
             max_players: 6,
-            players: (0..player_count).map(|_| Player::new()).collect(),
+            players,
             deck: new_deck,
             community_cards: Vec::new(),
             turn_count: 0,
-            pot_value: 0.0,
-            entry_cost: entry_cost,
+            pot_value: 0,
+            blinds,
+            positions: Positions::first_hand(&in_play, 0),
+            rng_: StdRng::from_rng(rng).expect("seeding from another generator"),
         };
-        game.distribute_cards_to_players();
+        game.begin_hand();
         game
     }
+    /// Moves the button and blinds on, reshuffles and deals the next hand.
+    /// Returns false, dealing nothing, once fewer than two players have
+    /// chips.
+    pub fn new_hand(&mut self) -> bool {
+        let in_play: Vec<bool> = self.players.iter().map(|pl| pl.balance > 0).collect();
+        self.positions = self
+            .positions
+            .and_then(|positions| positions.next_hand(&in_play));
+        if self.positions.is_none() {
+            return false;
+        }
+        self.deck = Deck::new(self.deck.pack_count);
+        self.deck.shuffle_with(&mut self.rng_);
+        self.community_cards.clear();
+        self.turn_count = 0;
+        self.pot_value = 0;
+        for pl in &mut self.players {
+            pl.cards.clear();
+            pl.decisions.clear();
+            pl.wagered = 0;
+        }
+        self.begin_hand();
+        true
+    }
+    // Sits out busted players, deals and takes the antes
+    fn begin_hand(&mut self) {
+        for pl in &mut self.players {
+            pl.is_folded = pl.balance == 0;
+        }
+        self.distribute_cards_to_players();
+        let Some(positions) = self.positions else {
+            return;
+        };
+        match self.blinds.ante {
+            None => {}
+            Some(Ante::PerPlayer(ante)) => {
+                for seat in 0..self.players.len() {
+                    if !self.players[seat].is_folded {
+                        self.post(seat, ante);
+                    }
+                }
+            }
+            Some(Ante::BigBlind(ante)) => {
+                let seat = positions.big_blind;
+                let behind_blind = self.players[seat]
+                    .balance
+                    .saturating_sub(self.blinds.big_blind);
+                self.post(seat, ante.min(behind_blind));
+            }
+        }
+    }
+    // Moves up to `amount` of a player's chips into the pot as dead money
+    fn post(&mut self, seat: usize, amount: u32) {
+        let pl = &mut self.players[seat];
+        let amount = amount.min(pl.balance);
+        pl.wager(amount);
+        self.pot_value += amount;
+    }
     pub fn distribute_cards_to_players(&mut self) {
         // Ensure there are enough cards in the deck for all players
         if self.deck.cards.len() < self.player_count as usize * 2 {
             panic!("Not enough cards in the deck to distribute to players.");
         }
 
-        // Distribute cards to each player
-        for player in &mut self.players {
+        // Distribute cards to each player still in
+        for player in self.players.iter_mut().filter(|pl| !pl.is_folded) {
             for _ in 0..2 {
                 if let Some(card) = self.deck.cards.pop() {
                     player.add_card(card);
@@ -72,15 +156,38 @@ impl Game {
             }
         }
     }
-    fn display_game_for_player(player: &Player) {
-        print!("{}: ", player.nickname);
+    fn display_game_for_player(player: &Player, position: &str) {
+        print!(
+            "{}{} ({} chips): ",
+            player.nickname, position, player.balance
+        );
         for dec in &player.decisions {
             print!("{}, ", dec.decision_type);
         }
-        print!("\n");
+        println!();
+    }
+
+    // Button and blind tags for a seat, e.g. " [button, small blind]"
+    fn position_label(&self, seat: usize) -> String {
+        let Some(positions) = self.positions else {
+            return String::new();
+        };
+        let tags: Vec<&str> = [
+            (positions.button == seat, "button"),
+            (positions.small_blind == Some(seat), "small blind"),
+            (positions.big_blind == seat, "big blind"),
+        ]
+        .into_iter()
+        .filter_map(|(holds, tag)| holds.then_some(tag))
+        .collect();
+        if tags.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", tags.join(", "))
+        }
     }
 
-    pub fn check_hand_rank(&self, cards: &Vec<Card>) {}
+    pub fn check_hand_rank(&self, _cards: &[Card]) {}
     fn release_card(&mut self) {
         // Ensure there are enough cards in the deck for all players
         if self.deck.cards.len() < self.player_count as usize * 2 {
@@ -91,97 +198,122 @@ impl Game {
             self.community_cards.push(card);
         }
     }
-    pub fn decision_round(&mut self, round_number: u8) {
-        let mut minimum_wager_round = 0.0;
-        for pl in &mut self.players {
+    pub fn decision_round(&mut self, _round_number: u8) {
+        let stacks: Vec<u32> = self.players.iter().map(|pl| pl.balance).collect();
+        let folded: Vec<bool> = self.players.iter().map(|pl| pl.is_folded).collect();
+        let Some(positions) = self.positions else {
+            return;
+        };
+        let mut round = if self.community_cards.is_empty() {
+            let mut blinds = Vec::new();
+            if let Some(seat) = positions.small_blind {
+                blinds.push((seat, self.blinds.small_blind));
+            }
+            blinds.push((positions.big_blind, self.blinds.big_blind));
+            BettingRound::with_blinds(
+                &stacks,
+                &folded,
+                &blinds,
+                self.blinds.big_blind,
+                positions.first_to_act_preflop(),
+            )
+        } else {
+            BettingRound::new(
+                &stacks,
+                &folded,
+                self.blinds.big_blind,
+                positions.first_to_act_postflop(),
+            )
+        };
+        for (seat, pl) in self.players.iter_mut().enumerate() {
+            let posted = round.committed(seat);
+            pl.wager(posted);
+            self.pot_value += posted;
+        }
+        while let Some(seat) = round.to_act() {
+            let pl = &self.players[seat];
             println!("Player {} must choose what he does: ", pl.nickname);
             print!("Player {} has the following cards: ", pl.nickname);
             //Print cards
             for card in &pl.cards {
                 print!("{}, ", card.to_clean_name());
             }
-            println!("\nCurrent minimum wager is {}", minimum_wager_round);
-            println!("1) Fold");
-            println!("2) Check");
-            println!("3) Wager");
-            loop {
-                println!("Your choice: ");
-                let mut input = String::new();
-                io::stdin()
-                    .read_line(&mut input)
-                    .expect("Failed to read line");
-
-                match input.trim() {
-                    "1" => {
-                        if round_number == 0 {
-                            pl.decisions.push(Decision::new(DecisionType::Fold, 0.0));
-                        } else if round_number == 1 {
-                            pl.decisions.push(Decision::new(DecisionType::Fold, 0.0));
-                        } else if round_number == 2 {
-                            pl.decisions.push(Decision::new(DecisionType::Fold, 0.0));
-                        } else if round_number == 3 {
-                            pl.decisions.push(Decision::new(DecisionType::Fold, 0.0));
-                        }
-                        pl.fold();
-                        break;
-                    }
-                    "2" => {
-                        if round_number == 0 {
-                            pl.decisions.push(Decision::new(DecisionType::Check, 0.0));
-                        } else if round_number == 1 {
-                            pl.decisions.push(Decision::new(DecisionType::Check, 0.0));
-                        } else if round_number == 2 {
-                            pl.decisions.push(Decision::new(DecisionType::Check, 0.0));
-                        } else if round_number == 3 {
-                            pl.decisions.push(Decision::new(DecisionType::Check, 0.0));
-                        }
-                        pl.check(minimum_wager_round);
-                        self.pot_value += minimum_wager_round;
-                        break;
-                    }
-                    "3" => {
-                        let wager_amount: u32 = loop {
-                            println!("Enter wager amount (1% to 100% of your balance): ");
-                            let mut wager_input = String::new();
-                            io::stdin()
-                                .read_line(&mut wager_input)
-                                .expect("Failed to read line");
-
-                            match wager_input.trim().parse() {
-                                Ok(amount) if (1..=100).contains(&amount) => break amount,
-                                Ok(_) => {
-                                    println!("Invalid wager amount. Must be between 1 and 100.")
-                                }
-                                Err(_) => println!("Invalid input. Please enter a number."),
-                            }
-                        };
-                        let wager_value = pl.balance * wager_amount as f32 / 100.0;
-                        //Set new minimum wager made by other player
-                        if (minimum_wager_round < wager_value) {
-                            minimum_wager_round = wager_value;
-                        }
-                        if round_number == 0 {
-                            pl.decisions.push(Decision::new(DecisionType::Call, 0.0));
-                        } else if round_number == 1 {
-                            pl.decisions.push(Decision::new(DecisionType::Call, 0.0));
-                        } else if round_number == 2 {
-                            pl.decisions.push(Decision::new(DecisionType::Call, 0.0));
-                        } else if round_number == 3 {
-                            pl.decisions.push(Decision::new(DecisionType::Call, 0.0));
-                        }
-                        pl.wager(minimum_wager_round + wager_value);
-                        self.pot_value += minimum_wager_round + wager_value;
-                        break;
-                    }
-                    _ => println!("Invalid input. Please enter 1, 2, or 3."),
+            println!();
+            self.display_threats(seat);
+            println!(
+                "Pot: {}, current bet: {}, your stack: {}",
+                self.pot_value + round.pot(),
+                round.current_bet(),
+                round.stack(seat)
+            );
+            let legal = round.legal_actions();
+            let action = loop {
+                let action = Self::read_action(&legal);
+                match round.apply(action) {
+                    Ok(()) => break action,
+                    Err(e) => println!("Cannot {}: {}.", action, e),
                 }
-            }
+            };
+            let pl = &mut self.players[seat];
+            let put_in = pl.balance - round.stack(seat);
+            pl.wager(put_in);
+            pl.is_folded = round.is_folded(seat);
+            let decision_type = match action {
+                Action::Fold => DecisionType::Fold,
+                Action::Check => DecisionType::Check,
+                Action::Call => DecisionType::Call,
+                Action::Bet(_) => DecisionType::Bet,
+                Action::Raise(_) => DecisionType::Raise,
+                Action::AllIn => DecisionType::AllIn,
+            };
+            pl.decisions.push(Decision::new(decision_type, put_in));
+            self.pot_value += put_in;
             clear_console();
         }
     }
+    // Prompts until one of the `legal` actions is picked, with an amount for
+    // bets and raises.
+    fn read_action(legal: &[LegalAction]) -> Action {
+        for (i, option) in legal.iter().enumerate() {
+            match option {
+                LegalAction::Fold => println!("{}) Fold", i + 1),
+                LegalAction::Check => println!("{}) Check", i + 1),
+                LegalAction::Call(amount) => println!("{}) Call {}", i + 1, amount),
+                LegalAction::Bet { min, max } => println!("{}) Bet {} to {}", i + 1, min, max),
+                LegalAction::Raise { min, max } => {
+                    println!("{}) Raise to {} to {}", i + 1, min, max)
+                }
+            }
+        }
+        loop {
+            println!("Your choice: ");
+            let choice = read_number().and_then(|n| legal.get(n.checked_sub(1)? as usize));
+            let (min, max) = match choice {
+                Some(LegalAction::Fold) => return Action::Fold,
+                Some(LegalAction::Check) => return Action::Check,
+                Some(LegalAction::Call(_)) => return Action::Call,
+                Some(LegalAction::Bet { min, max } | LegalAction::Raise { min, max }) => {
+                    (*min, *max)
+                }
+                None => {
+                    println!("Invalid input. Please enter 1 to {}.", legal.len());
+                    continue;
+                }
+            };
+            println!("Enter the total amount ({} to {}): ", min, max);
+            match read_number() {
+                Some(amount) if amount == max => return Action::AllIn,
+                Some(amount) if matches!(choice, Some(LegalAction::Bet { .. })) => {
+                    return Action::Bet(amount)
+                }
+                Some(amount) => return Action::Raise(amount),
+                None => println!("Invalid input. Please enter a number."),
+            }
+        }
+    }
     pub fn dynamic_pad_string(input: &str, padding_char: char) -> String {
         // Get terminal width
-        let term_width = if let Some((w, h)) = dimensions() {
+        let term_width = if let Some((w, _h)) = dimensions() {
             w
         } else {
             // Default to a reasonable width if terminal dimensions can't be determined
@@ -202,10 +334,57 @@ impl Game {
         // Concatenate the padded string
         format!("{}{}{}", left_padding, input, right_padding)
     }
-    fn display_danger() {
+    // The public part of the dangers: what the board allows, and the nuts
+    fn display_danger(&self) {
+        if self.community_cards.len() < 3 {
+            return;
+        }
+        println!();
         println!("=== Dangers from other player's hands based on community cards ===");
+        println!("Board: {}", analyze_board(&self.community_cards));
+        let nuts = ranked_holdings(&[], &self.community_cards, self.deck.pack_count as usize)
+            .into_iter()
+            .min_by_key(|&(_, rank)| rank);
+        if let Some((holding, rank)) = nuts {
+            println!(
+                "Nuts: {} (e.g. {}{})",
+                get_rank_category(rank),
+                holding[0],
+                holding[1]
+            );
+        }
         println!("============");
     }
+    // The made hands that beat one player's hand, for their own turn screen
+    // only
+    fn display_threats(&self, seat: usize) {
+        let pl = &self.players[seat];
+        if self.community_cards.len() < 3 || pl.cards.len() != 2 {
+            return;
+        }
+        let mut cards = pl.cards.clone();
+        cards.extend_from_slice(&self.community_cards);
+        let category = get_rank_category(evaluate(&cards));
+        let threats = hands_that_beat(
+            &pl.cards,
+            &self.community_cards,
+            self.deck.pack_count as usize,
+        );
+        if threats.is_empty() {
+            println!("Your hand ({}): nothing beats it", category);
+            return;
+        }
+        let threats: Vec<String> = threats
+            .iter()
+            .map(|threat| {
+                format!(
+                    "{} ({} combos, e.g. {}{})",
+                    threat.category, threat.combos, threat.example[0], threat.example[1]
+                )
+            })
+            .collect();
+        println!("Your hand ({}): beaten by {}", category, threats.join(", "));
+    }
     fn display_game(&mut self) {
         clear_console();
         println!(
@@ -226,12 +405,39 @@ impl Game {
             comm_cards += ", ";
         }
         print!("{}", Self::dynamic_pad_string(&comm_cards, ' '));
+        self.display_danger();
 
         println!("{}", Self::dynamic_pad_string("\nPlayers ", ' '));
-        for pl in &mut self.players {
-            Self::display_game_for_player(pl); // <-- Modified line
+        for (seat, pl) in self.players.iter().enumerate() {
+            Self::display_game_for_player(pl, &self.position_label(seat));
+        }
+        println!();
+    }
+    /// Ranks the hands still in, pays out the main and side pots and
+    /// credits the winners' balances. Returns the chips each seat won.
+    pub fn showdown(&mut self) -> Vec<u32> {
+        for pl in self.players.iter_mut().filter(|pl| !pl.is_folded) {
+            let mut combined_cards: Vec<Card> = Vec::new();
+            combined_cards.extend(self.community_cards.clone()); // Clone the community cards
+            combined_cards.extend(pl.cards.clone()); // Use a reference to avoid moving pl.cards
+
+            // The shoe holds several packs, so the hand may repeat cards
+            pl.hand_rank = evaluate(&combined_cards);
+        }
+        let ranks: Vec<i32> = self.players.iter().map(|pl| pl.hand_rank).collect();
+        // Odd chips start from the seat left of the button
+        let first_odd_chip = self
+            .positions
+            .map_or(0, |positions| positions.first_to_act_postflop());
+        let won = settle(&mut self.players, &ranks, first_odd_chip);
+        self.pot_value = 0;
+        //Endgame
+        for (pl, &chips) in self.players.iter().zip(&won) {
+            if chips > 0 {
+                println!("Player {} won {}!", pl.nickname, chips);
+            }
         }
-        println!("");
+        won
     }
     pub fn play_turn(&mut self) {
         println!("turn #: {}", self.turn_count);
@@ -246,48 +452,13 @@ impl Game {
             self.display_game();
             self.decision_round(self.turn_count);
             self.display_game();
-        } else if self.turn_count == 1 {
-            self.release_card();
-            self.display_game();
-            self.decision_round(self.turn_count);
-            self.display_game();
-        } else if self.turn_count == 2 {
+        } else if self.turn_count == 1 || self.turn_count == 2 {
             self.release_card();
             self.display_game();
             self.decision_round(self.turn_count);
             self.display_game();
         } else if self.turn_count == 3 {
-            for pl in &mut self.players {
-                let mut combined_cards: Vec<Card> = Vec::new();
-                combined_cards.extend(self.community_cards.clone()); // Clone the community cards
-                combined_cards.extend(pl.cards.clone()); // Use a reference to avoid moving pl.cards
-
-                let rank = evaluator7::evaluate_7cards(
-                    combined_cards[0].int32(),
-                    combined_cards[1].int32(),
-                    combined_cards[2].int32(),
-                    combined_cards[3].int32(),
-                    combined_cards[4].int32(),
-                    combined_cards[5].int32(),
-                    combined_cards[6].int32(),
-                );
-                pl.hand_rank = rank;
-            }
-            //Endgame
-            let min_player = self
-                .players
-                .iter()
-                .min_by(|&a, &b| a.hand_rank.cmp(&b.hand_rank));
-            if let Some(min_player) = self
-                .players
-                .iter()
-                .min_by(|&a, &b| a.hand_rank.cmp(&b.hand_rank))
-            {
-                println!("Player {} won!", min_player.nickname);
-                // Print other details if needed
-            } else {
-                println!("No players found");
-            }
+            self.showdown();
         }
 
         self.turn_count += 1;
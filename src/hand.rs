@@ -1,4 +1,5 @@
-use crate::card::Card;
+use crate::card::{Card, Suit};
+use crate::isomorphism::SuitPermutation;
 use std::cmp::Ord;
 
 /// All hand rank classes that a 5-card hand can be worth in Texas Hold'em.
@@ -38,6 +39,11 @@ const SUITBIT_BY_ID: [i32; 52] = [
     0x1, 0x8, 0x40, 0x200,
 ];
 
+/// Suit counts, per-suit rank bits and rank counts of a set of distinct cards:
+/// everything `evaluate_hand` needs. Cards can be added and removed one at a
+/// time, and a board shared by several players can be combined with each
+/// player's hole cards without hashing the board again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct Hand {
     size_: u8,
     suit_hash: i32,
@@ -46,16 +52,94 @@ pub struct Hand {
 }
 
 impl Hand {
+    /// A hand without cards.
+    pub const fn new() -> Hand {
+        Hand {
+            size_: 0,
+            suit_hash: 0,
+            suit_binary: [0; 4],
+            quinary_: [0; 13],
+        }
+    }
+
+    pub fn from_cards(cards: &[Card]) -> Hand {
+        let mut hand = Hand::new();
+        for &card in cards {
+            hand.add_card(card);
+        }
+        hand
+    }
+
+    /// Adds a card, which must not already be in the hand.
+    pub fn add_card(&mut self, card: Card) {
+        let id = card.int();
+        self.suit_hash += SUITBIT_BY_ID[id];
+        self.suit_binary[id & 0x3] |= BINARIES_BY_ID[id];
+        self.quinary_[id >> 2] += 1;
+        self.size_ += 1;
+    }
+
+    /// Removes a card that is in the hand.
+    pub fn remove_card(&mut self, card: Card) {
+        let id = card.int();
+        self.suit_hash -= SUITBIT_BY_ID[id];
+        self.suit_binary[id & 0x3] &= !BINARIES_BY_ID[id];
+        self.quinary_[id >> 2] -= 1;
+        self.size_ -= 1;
+    }
+
+    /// The union of two hands with no card in common.
+    pub fn combine(&self, other: &Hand) -> Hand {
+        let mut quinary_ = self.quinary_;
+        for (count, other_count) in quinary_.iter_mut().zip(other.quinary_) {
+            *count += other_count;
+        }
+        Hand {
+            size_: self.size_ + other.size_,
+            suit_hash: self.suit_hash + other.suit_hash,
+            suit_binary: [
+                self.suit_binary[0] | other.suit_binary[0],
+                self.suit_binary[1] | other.suit_binary[1],
+                self.suit_binary[2] | other.suit_binary[2],
+                self.suit_binary[3] | other.suit_binary[3],
+            ],
+            quinary_,
+        }
+    }
+
+    /// This hand, typically a board, plus two hole cards.
+    pub fn with_hole_cards(&self, a: Card, b: Card) -> Hand {
+        let mut hand = *self;
+        hand.add_card(a);
+        hand.add_card(b);
+        hand
+    }
+
+    /// This hand with its suits relabelled, as if each card had been passed
+    /// through [`Card::relabel_suits`].
+    pub fn relabel_suits(&self, permutation: &SuitPermutation) -> Hand {
+        let mut hand = *self;
+        hand.suit_hash = 0;
+        for suit in Suit::ALL {
+            let image = permutation.apply(suit).index();
+            hand.suit_binary[image] = self.suit_binary[suit.index()];
+            // Suit counts are packed three bits per suit
+            let count = (self.suit_hash >> (3 * suit.index())) & 0x7;
+            hand.suit_hash += count << (3 * image);
+        }
+        hand
+    }
+
     pub fn from_card_vector(cards: Vec<Card>) -> Hand {
         let mut suit_hash = 0;
         let mut suit_binary: [i32; 4] = [0; 4];
         let mut quinary_: [u8; 13] = [0; 13];
         let mut size_: u8 = 0;
         for card in cards.iter() {
-            suit_hash = suit_hash + SUITBIT_BY_ID[card.int()];
-            suit_binary[card.int() & 0x3] |= BINARIES_BY_ID[card.int()];
-            quinary_[(card.int() >> 2)] = quinary_[(card.int() >> 2)] + 1;
-            size_ = size_ + 1;
+            suit_hash += SUITBIT_BY_ID[card.int()];
+            suit_binary[card.suit().index()] |= BINARIES_BY_ID[card.int()];
+            quinary_[card.rank().index()] += 1;
+            size_ += 1;
         }
         Hand {
             size_,
@@ -69,10 +153,10 @@ impl Hand {
         let mut suit_binary: [i32; 4] = [0; 4];
         let mut quinary_: [u8; 13] = [0; 13];
         let mut size_: u8 = 0;
-        suit_hash = suit_hash + SUITBIT_BY_ID[card.int()];
-        suit_binary[card.int() & 0x3] |= BINARIES_BY_ID[card.int()];
-        quinary_[(card.int() >> 2)] = quinary_[(card.int() >> 2)] + 1;
-        size_ = size_ + 1;
+        suit_hash += SUITBIT_BY_ID[card.int()];
+        suit_binary[card.suit().index()] |= BINARIES_BY_ID[card.int()];
+        quinary_[card.rank().index()] += 1;
+        size_ += 1;
         Hand {
             size_,
             suit_hash,
@@ -82,17 +166,78 @@ impl Hand {
     }
 
     pub const fn get_suit_hash(&self) -> &i32 {
-        return &self.suit_hash;
+        &self.suit_hash
     }
 
     pub const fn size(&self) -> &u8 {
-        return &self.size_;
+        &self.size_
     }
     pub const fn get_suit_binary(&self) -> &[i32; 4] {
-        return &self.suit_binary;
+        &self.suit_binary
     }
 
     pub const fn get_quinary(&self) -> &[u8; 13] {
-        return &self.quinary_;
+        &self.quinary_
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::{evaluate, evaluate_hand};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn shuffled_decks(seed: u64) -> impl Iterator<Item = Vec<Card>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut deck: Vec<Card> = (0..52).map(|id| Card::from_id(id).unwrap()).collect();
+        (0..2_000).map(move |_| {
+            deck.shuffle(&mut rng);
+            deck[..7].to_vec()
+        })
+    }
+
+    #[test]
+    fn a_combined_hand_evaluates_like_its_cards() {
+        for cards in shuffled_decks(15) {
+            let flat = Hand::from_cards(&cards);
+            let board = Hand::from_cards(&cards[2..]);
+            let hole = Hand::from_cards(&cards[..2]);
+            assert_eq!(board.combine(&hole), flat);
+            assert_eq!(hole.combine(&board), flat);
+            assert_eq!(board.with_hole_cards(cards[0], cards[1]), flat);
+            assert_eq!(Hand::from_card_vector(cards.clone()), flat);
+            assert_eq!(
+                evaluate_hand(&board.combine(&hole)).value(),
+                evaluate(&cards)
+            );
+        }
+    }
+
+    #[test]
+    fn remove_card_undoes_add_card() {
+        for cards in shuffled_decks(16) {
+            let before = Hand::from_cards(&cards[..6]);
+            let mut hand = before;
+            hand.add_card(cards[6]);
+            assert_eq!(*hand.size(), 7);
+            hand.remove_card(cards[6]);
+            assert_eq!(hand, before);
+            // Taking out a card in the middle leaves the other six
+            let mut hand = Hand::from_cards(&cards);
+            hand.remove_card(cards[3]);
+            let rest: Vec<Card> = cards
+                .iter()
+                .copied()
+                .filter(|&card| card != cards[3])
+                .collect();
+            assert_eq!(hand, Hand::from_cards(&rest));
+        }
+        let mut hand = Hand::new();
+        let card = Card::from_name("Ah").unwrap();
+        hand.add_card(card);
+        hand.remove_card(card);
+        assert_eq!(hand, Hand::new());
     }
 }
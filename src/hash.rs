@@ -18,5 +18,5 @@ pub fn hash_quinary(q: &[u8], mut k: i32) -> u32 {
         }
     }
 
-    return sum;
+    sum
 }
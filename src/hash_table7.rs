@@ -1,4 +1,4 @@
-pub const NOFLUSH7: [i32; 49205] = [
+pub static NOFLUSH7: [i32; 49205] = [
     11, 23, 11, 167, 23, 11, 167, 179, 23, 12, 168, 191, 180, 24, 35, 35, 35, 36, 11, 167, 23, 11,
     167, 179, 23, 12, 168, 2468, 180, 24, 168, 191, 192, 180, 35, 35, 36, 11, 167, 179, 23, 12,
     169, 2468, 181, 24, 168, 2479, 2600, 180, 191, 193, 192, 35, 36, 13, 169, 203, 181, 25, 169,
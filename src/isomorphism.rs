@@ -0,0 +1,235 @@
+use crate::card::{Card, Suit};
+use std::cmp::Ordering;
+use std::fmt;
+
+// Hold'em treats the four suits alike, so relabelling them (say clubs and
+// spades swap) never changes how hands compare. Spots that differ only by such
+// a relabelling are strategically identical: 22,100 flops fall into 1,755
+// classes, 1,326 pairs of hole cards into 169. `canonicalize` picks one
+// representative of each class so caches and tables can be keyed on it.
+
+/// A relabelling of the four suits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuitPermutation {
+    // `images_[suit.index()]` is the suit `suit` becomes
+    images_: [Suit; 4],
+}
+
+impl SuitPermutation {
+    pub const IDENTITY: SuitPermutation = SuitPermutation { images_: Suit::ALL };
+
+    /// The permutation sending `Suit::ALL[i]` to `images[i]`, if no suit
+    /// appears twice.
+    pub fn new(images: [Suit; 4]) -> Option<SuitPermutation> {
+        let distinct = (0..4).all(|i| !images[..i].contains(&images[i]));
+        distinct.then_some(SuitPermutation { images_: images })
+    }
+
+    /// All 24 permutations, the identity first.
+    pub fn all() -> [SuitPermutation; 24] {
+        let mut all = [SuitPermutation::IDENTITY; 24];
+        let mut next = 0;
+        for a in Suit::ALL {
+            for b in Suit::ALL {
+                for c in Suit::ALL {
+                    for d in Suit::ALL {
+                        if let Some(permutation) = SuitPermutation::new([a, b, c, d]) {
+                            all[next] = permutation;
+                            next += 1;
+                        }
+                    }
+                }
+            }
+        }
+        all
+    }
+
+    pub const fn apply(&self, suit: Suit) -> Suit {
+        self.images_[suit.index()]
+    }
+
+    /// The permutation that undoes this one.
+    pub fn inverse(&self) -> SuitPermutation {
+        let mut images_ = Suit::ALL;
+        for suit in Suit::ALL {
+            images_[self.apply(suit).index()] = suit;
+        }
+        SuitPermutation { images_ }
+    }
+}
+
+impl fmt::Display for SuitPermutation {
+    /// Lists where each suit goes, e.g. "c->s d->d h->h s->c".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, suit) in Suit::ALL.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}->{}", suit, self.apply(*suit))?;
+        }
+        Ok(())
+    }
+}
+
+/// The representative of a class of (hole cards, board) spots that are equal
+/// up to relabelling suits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalSpot {
+    /// Highest card first.
+    pub hole_cards: Vec<Card>,
+    /// Highest card first.
+    pub board: Vec<Card>,
+    /// Relabelling that turns the original spot into this one; its inverse
+    /// turns this one back.
+    pub permutation: SuitPermutation,
+    // Permutations that leave this spot unchanged, from 1 to 24
+    symmetries_: usize,
+}
+
+impl CanonicalSpot {
+    /// How many distinct spots share this representative.
+    pub fn class_size(&self) -> usize {
+        24 / self.symmetries_
+    }
+}
+
+/// Relabels suits so that equivalent spots give the same result. Hole cards
+/// and board are each taken as unordered sets; of all 24 relabellings the one
+/// giving the lowest cards, hole cards first, is chosen.
+pub fn canonicalize(hole_cards: &[Card], board: &[Card]) -> CanonicalSpot {
+    let relabelled = |cards: &[Card], permutation: &SuitPermutation| {
+        let mut cards: Vec<Card> = cards
+            .iter()
+            .map(|card| card.relabel_suits(permutation))
+            .collect();
+        cards.sort_unstable_by(|a, b| b.cmp(a));
+        cards
+    };
+
+    let mut best = CanonicalSpot {
+        hole_cards: relabelled(hole_cards, &SuitPermutation::IDENTITY),
+        board: relabelled(board, &SuitPermutation::IDENTITY),
+        permutation: SuitPermutation::IDENTITY,
+        symmetries_: 1,
+    };
+    for permutation in &SuitPermutation::all()[1..] {
+        let hole = relabelled(hole_cards, permutation);
+        if hole > best.hole_cards {
+            continue;
+        }
+        let board = relabelled(board, permutation);
+        match (&hole, &board).cmp(&(&best.hole_cards, &best.board)) {
+            Ordering::Less => {
+                best = CanonicalSpot {
+                    hole_cards: hole,
+                    board,
+                    permutation: *permutation,
+                    symmetries_: 1,
+                };
+            }
+            Ordering::Equal => best.symmetries_ += 1,
+            Ordering::Greater => {}
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_set::CardSet;
+    use crate::notation::parse_cards;
+    use itertools::Itertools;
+    use std::collections::HashMap;
+
+    // Class sizes of every class met, and how many spots fell into each
+    fn classes(
+        spots: impl Iterator<Item = (Vec<Card>, Vec<Card>)>,
+    ) -> HashMap<CanonicalSpot, usize> {
+        let mut classes = HashMap::new();
+        for (hole_cards, board) in spots {
+            let mut spot = canonicalize(&hole_cards, &board);
+            spot.permutation = SuitPermutation::IDENTITY;
+            *classes.entry(spot).or_insert(0) += 1;
+        }
+        classes
+    }
+
+    #[test]
+    fn flops_fall_into_1755_classes() {
+        let flops = CardSet::full()
+            .iter()
+            .combinations(3)
+            .map(|flop| (Vec::new(), flop));
+        let classes = classes(flops);
+        assert_eq!(classes.len(), 1755);
+        for (spot, &count) in &classes {
+            assert_eq!(spot.class_size(), count, "{:?}", spot.board);
+        }
+        assert_eq!(
+            classes.keys().map(CanonicalSpot::class_size).sum::<usize>(),
+            22100
+        );
+    }
+
+    #[test]
+    fn hole_cards_fall_into_169_classes() {
+        let pairs = CardSet::full()
+            .iter()
+            .combinations(2)
+            .map(|pair| (pair, Vec::new()));
+        let classes = classes(pairs);
+        assert_eq!(classes.len(), 169);
+        for (spot, &count) in &classes {
+            assert_eq!(spot.class_size(), count, "{:?}", spot.hole_cards);
+        }
+        assert_eq!(
+            classes.keys().map(CanonicalSpot::class_size).sum::<usize>(),
+            1326
+        );
+    }
+
+    #[test]
+    fn the_permutation_maps_the_spot_to_its_representative() {
+        let relabel = |cards: &[Card], permutation: &SuitPermutation| {
+            let mut cards: Vec<Card> = cards
+                .iter()
+                .map(|card| card.relabel_suits(permutation))
+                .collect();
+            cards.sort_unstable_by(|a, b| b.cmp(a));
+            cards
+        };
+        for (hole, board) in [
+            ("Ah Kh", "Qh 7s 2d"),
+            ("9c 9d", "9h 5c 5d Js"),
+            ("2s 7d", "Ts 8s 3s Kd Kc"),
+            ("Jc Tc", ""),
+        ] {
+            let hole_cards = parse_cards(hole).unwrap();
+            let board = parse_cards(board).unwrap();
+            let spot = canonicalize(&hole_cards, &board);
+            assert_eq!(relabel(&hole_cards, &spot.permutation), spot.hole_cards);
+            assert_eq!(relabel(&board, &spot.permutation), spot.board);
+            let inverse = spot.permutation.inverse();
+            assert_eq!(
+                relabel(&spot.hole_cards, &inverse),
+                relabel(&hole_cards, &SuitPermutation::IDENTITY)
+            );
+            assert_eq!(
+                relabel(&spot.board, &inverse),
+                relabel(&board, &SuitPermutation::IDENTITY)
+            );
+            // Any relabelling of the spot has the same representative
+            for permutation in SuitPermutation::all() {
+                let other = canonicalize(
+                    &relabel(&hole_cards, &permutation),
+                    &relabel(&board, &permutation),
+                );
+                assert_eq!(
+                    (&other.hole_cards, &other.board),
+                    (&spot.hole_cards, &spot.board)
+                );
+            }
+        }
+    }
+}
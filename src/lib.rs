@@ -0,0 +1,74 @@
+//! Texas Hold'em card primitives, a perfect-hash hand evaluator, a Monte Carlo
+//! probability engine and a small interactive game engine.
+
+pub mod batch;
+pub mod best_hand;
+pub mod betting;
+pub mod blinds;
+pub mod card;
+pub mod card_set;
+pub mod deck;
+mod dptables;
+pub mod draws;
+pub mod enumeration;
+pub mod evaluator;
+pub mod evaluator5;
+pub mod evaluator6;
+pub mod evaluator7;
+pub mod game;
+pub mod hand;
+mod hash;
+mod hash_table5;
+mod hash_table6;
+mod hash_table7;
+mod hashtable;
+pub mod isomorphism;
+pub mod multideck;
+pub mod notation;
+pub mod outs;
+pub mod player;
+pub mod pot;
+pub mod preflop;
+pub mod probability;
+pub mod range;
+pub mod rank;
+mod seven_four_six_two;
+pub mod simulation;
+pub mod starting_hand;
+pub mod strength;
+pub mod texture;
+
+pub use batch::{evaluate_batch, evaluate_batch_on_board};
+pub use best_hand::{best_five, best_hand, BestHand};
+pub use betting::{Action, BettingError, BettingRound, LegalAction};
+pub use blinds::{Ante, Blinds, Positions};
+pub use card::{Card, CardParseError, CardRank, Suit};
+pub use card_set::CardSet;
+pub use deck::Deck;
+pub use draws::{find_draws, Draw, DrawKind, Draws};
+pub use evaluator::{evaluate, evaluate_cards, evaluate_hand};
+pub use evaluator5::evaluate_5cards;
+pub use evaluator6::evaluate_6cards;
+pub use evaluator7::evaluate_7cards;
+pub use game::Game;
+pub use hand::Hand;
+pub use isomorphism::{canonicalize, CanonicalSpot, SuitPermutation};
+pub use multideck::evaluate_multideck;
+pub use notation::{parse_cards, parse_cards_from_packs, Board, NotationError, Street};
+pub use outs::{outs, Out, Outs};
+pub use player::Player;
+pub use pot::{award_pots, build_pots, settle, Pot};
+pub use preflop::{preflop_table, PreflopTable};
+pub use probability::{
+    calculate_equity_vs_ranges, calculate_equity_vs_ranges_with_config,
+    calculate_hand_probabilities, calculate_hand_probabilities_with_config, calculate_range_equity,
+    calculate_range_equity_with_config, calculate_win_probability,
+    calculate_win_probability_with_config, get_ordered_probabilities, EquityResult,
+    HandProbabilities, RangeEquityError,
+};
+pub use range::{Range, RangeParseError};
+pub use rank::{describe_rank_category, get_rank_category, RankCategory};
+pub use simulation::SimulationConfig;
+pub use starting_hand::StartingHand;
+pub use strength::{relative_strength, RelativeStrength};
+pub use texture::{analyze_board, hands_that_beat, BoardTexture, Pairing, SuitPattern, Threat};
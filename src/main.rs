@@ -1,82 +1,154 @@
-// lib.rs
-mod card;
-mod deck;
-mod dptables;
-mod evaluator;
-mod evaluator7;
-mod game;
-mod hand;
-mod hash;
-mod hash_table7;
-mod hashtable;
-mod player;
-mod probability;
-mod rank;
-mod seven_four_six_two;
+use std::io;
+
+use rust_texas_holdem::notation::{self, Board};
+use rust_texas_holdem::probability;
+use rust_texas_holdem::{
+    best_hand, calculate_hand_probabilities, calculate_win_probability, describe_rank_category,
+    evaluate, find_draws, get_rank_category, outs, relative_strength, Card, Range,
+};
+
+// Describes the best five-card hand made from the hole cards and board so far.
+fn describe_current_hand(hole_cards: &[Card], community_cards: &[Card]) -> String {
+    let mut cards = hole_cards.to_vec();
+    cards.extend_from_slice(community_cards);
+    let rank = evaluate(&cards);
+    format!(
+        "{} (rank {})",
+        describe_rank_category(get_rank_category(rank)),
+        rank
+    )
+}
 
-use rand::seq::SliceRandom;
-use std::collections::HashMap;
-use std::io::{self, Read};
+fn format_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
-// Export relevant types for external use
-pub use card::Card;
-pub use deck::Deck;
-use evaluator7::evaluate_7cards;
-pub use game::Game;
-pub use player::Player;
-use probability::{calculate_hand_probabilities, calculate_win_probability};
-use rank::{describe_rank_category, get_rank_category, RankCategory};
+fn print_probabilities(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    num_players: usize,
+    pack_count: usize,
+) {
+    let hand_probs =
+        calculate_hand_probabilities(hole_cards, community_cards, num_players, pack_count);
+    for (category, prob) in probability::get_ordered_probabilities(&hand_probs) {
+        println!("{}: {:.2}%", category, prob);
+    }
 
-fn get_rank_of_7_perfect(cards: Vec<&str>) -> i32 {
-    // &Card::from_name("Tc".to_string()),
-    // &Card::from_name("Jc".to_string()),
-    // &Card::from_name("Qc".to_string()),
-    // &Card::from_name("Kc".to_string()),
-    // &Card::from_name("Ac".to_string()),
-    // &Card::from_name("2c".to_string()),
-    // &Card::from_name("9c".to_string()),
-    let rank = evaluate_7cards(
-        Card::from_name(cards[0].to_string()).id_,
-        Card::from_name(cards[1].to_string()).id_,
-        Card::from_name(cards[2].to_string()).id_,
-        Card::from_name(cards[3].to_string()).id_,
-        Card::from_name(cards[4].to_string()).id_,
-        Card::from_name(cards[5].to_string()).id_,
-        Card::from_name(cards[6].to_string()).id_,
+    let win_prob = calculate_win_probability(hole_cards, community_cards, num_players, pack_count);
+    println!(
+        "Win: {:.2}%  Tie: {:.2}%  Lose: {:.2}%  Equity: {:.2}%",
+        win_prob.win * 100.0,
+        win_prob.tie * 100.0,
+        win_prob.lose * 100.0,
+        win_prob.equity * 100.0
     );
-    rank
+    // Exact results have no sampling error to report
+    if win_prob.std_error > 0.0 {
+        let (low, high) = win_prob.confidence_interval();
+        println!(
+            "Equity 95% confidence interval: {:.2}% to {:.2}%",
+            low * 100.0,
+            high * 100.0
+        );
+    }
 }
-fn display_all_cards() -> Vec<String> {
-    let ranks = [
-        '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
-    ];
-    let suits = ['c', 'd', 'h', 's'];
-    let mut cards = Vec::new();
 
-    for (i, rank) in ranks.iter().enumerate() {
-        for (j, suit) in suits.iter().enumerate() {
-            let card_name = format!("{}{}", rank, suit);
-            let card_number = i * 4 + j + 1;
-            println!(
-                "{}. {}",
-                card_number,
-                Card::from_name(card_name.clone()).to_clean_name()
-            );
-            cards.push(card_name);
+fn print_draws(hole_cards: &[Card], community_cards: &[Card]) {
+    let draws = find_draws(hole_cards, community_cards);
+    if draws.is_empty() {
+        return;
+    }
+    let described: Vec<String> = draws
+        .draws
+        .iter()
+        .map(|draw| match draw.outs.len() {
+            0 => draw.kind.to_string(),
+            outs => format!("{} ({} outs)", draw.kind, outs),
+        })
+        .collect();
+    print!("\nDraws: {}", described.join(", "));
+    if draws.is_combo_draw() {
+        print!("; combo draw with {} outs", draws.outs().len());
+    }
+    println!();
+}
+
+// Lists the cards that would put us ahead of random opponents.
+fn print_outs(hole_cards: &[Card], community_cards: &[Card], num_players: usize) {
+    let opponents = vec![Range::random(); num_players.saturating_sub(1)];
+    let outs = outs(hole_cards, community_cards, &opponents);
+    if outs.count() == 0 {
+        println!("\nNo outs.");
+        return;
+    }
+    println!(
+        "\nOuts: {} ({} clean, {} tainted). Rule of {}: {:.0}%, exact: {:.2}%",
+        outs.count(),
+        outs.clean.len(),
+        outs.tainted.len(),
+        2 * outs.cards_to_come,
+        outs.rule_of_thumb(),
+        outs.hit_probability()
+    );
+    for (label, list) in [("Clean", &outs.clean), ("Tainted", &outs.tainted)] {
+        if list.is_empty() {
+            continue;
         }
+        let cards: Vec<String> = list
+            .iter()
+            .map(|out| format!("{} ({})", out.card, out.category))
+            .collect();
+        println!("{}: {}", label, cards.join(", "));
     }
-    cards
 }
 
-fn get_card_by_number(card_number: usize, all_cards: &Vec<String>) -> String {
-    if card_number > 0 && card_number <= all_cards.len() {
-        all_cards[card_number - 1].clone()
-    } else {
-        panic!("Invalid card number");
+// Reads cards for one street. Returns `None` when the user skips the street
+// and `Some(Err(..))` when the input is invalid or uses a card more often
+// than the packs hold it.
+fn read_street(
+    prompt: &str,
+    count: usize,
+    known_cards: &[Card],
+    pack_count: usize,
+) -> Option<Result<Vec<Card>, String>> {
+    let mut input = String::new();
+    println!("{}", prompt);
+    io::stdin().read_line(&mut input).unwrap();
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    let cards = match notation::parse_cards_from_packs(input, pack_count) {
+        Ok(cards) => cards,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    if cards.len() != count {
+        return Some(Err(format!(
+            "expected {} card(s), found {}",
+            count,
+            cards.len()
+        )));
+    }
+    let copies_in_play = |card: &Card| {
+        known_cards
+            .iter()
+            .chain(&cards)
+            .filter(|&known| known == card)
+            .count()
+    };
+    if let Some(card) = cards.iter().find(|&card| copies_in_play(card) > pack_count) {
+        return Some(Err(format!("card {} is already in play", card)));
     }
+    Some(Ok(cards))
 }
 
-fn calculatePersonalHand() {
+fn calculate_personal_hand() {
     // Input number of players
     let mut num_players_input = String::new();
     println!("Enter number of players:");
@@ -94,191 +166,130 @@ fn calculatePersonalHand() {
     println!("Enter number of card packs:");
     io::stdin().read_line(&mut pack_count_input).unwrap();
     let pack_count: usize = match pack_count_input.trim().parse() {
-        Ok(n) => n,
-        Err(_) => {
+        Ok(n) if n > 0 => n,
+        _ => {
             println!("Invalid input for number of card packs.");
             return;
         }
     };
 
-    // Display all cards with numbers
-    println!("\nAvailable cards:");
-    let all_cards = display_all_cards();
-
     // Input 2 cards
     let mut cards_input = String::new();
-    println!("\nEnter your 2 cards as numbers (e.g., 1,2):");
+    println!("\nEnter your 2 cards (e.g. AhKs or \"Ah Ks\"):");
     io::stdin().read_line(&mut cards_input).unwrap();
-    let card_numbers: Vec<usize> = cards_input
-        .trim()
-        .split(',')
-        .map(|s| s.trim().parse().unwrap())
-        .collect();
-    if card_numbers.len() != 2 {
-        println!(
-            "Invalid input for cards. Please enter exactly 2 card numbers in the format (1,2)."
-        );
-        return;
-    }
-
-    let cards: Vec<String> = card_numbers
-        .iter()
-        .map(|&num| get_card_by_number(num, &all_cards))
-        .collect();
-    println!("Your hole cards: {:?}", cards);
-
-    // Convert string cards to Card objects
-    let hole_cards: Vec<Card> = cards.iter().map(|s| Card::from_name(s.clone())).collect();
-    let community_cards: Vec<Card> = Vec::new();
-
-    // Calculate and display probabilities for hole cards
-    println!("\nProbabilities with hole cards:");
-    let hand_probs =
-        calculate_hand_probabilities(&hole_cards, &community_cards, num_players, pack_count);
-    for (category, prob) in probability::get_ordered_probabilities(&hand_probs) {
-        println!("{}: {:.2}%", category, prob);
-    }
-
-    let win_prob =
-        calculate_win_probability(&hole_cards, &community_cards, num_players, pack_count);
-    println!("Win probability: {:.2}%", win_prob);
-
-    // Initialize middle cards vector
-    let mut cards_middle: Vec<String> = Vec::new();
-    let mut community_cards: Vec<Card> = Vec::new();
-
-    // Ask for flop (3 cards)
-    let mut flop_input = String::new();
-    println!("\nEnter the flop (3 cards) as numbers (e.g., 3,4,5) or press Enter to skip:");
-    io::stdin().read_line(&mut flop_input).unwrap();
-    let flop_input = flop_input.trim();
-
-    if !flop_input.is_empty() {
-        let flop_numbers: Vec<usize> = flop_input
-            .split(',')
-            .map(|s| s.trim().parse().unwrap())
-            .collect();
-        if flop_numbers.len() != 3 {
-            println!("Invalid input for flop. Please enter exactly 3 card numbers in the format (3,4,5).");
+    // With several packs, both hole cards may be the same card
+    let hole_cards = match notation::parse_cards_from_packs(cards_input.trim(), pack_count) {
+        Ok(cards) if cards.len() == 2 => cards,
+        Ok(cards) => {
+            println!(
+                "Invalid input for cards: expected 2 cards, found {}.",
+                cards.len()
+            );
             return;
         }
-
-        let flop_cards: Vec<String> = flop_numbers
-            .iter()
-            .map(|&num| get_card_by_number(num, &all_cards))
-            .collect();
-        cards_middle.extend(flop_cards.clone());
-        println!("Flop: {:?}", cards_middle);
-
-        // Convert string cards to Card objects
-        community_cards = flop_cards
-            .iter()
-            .map(|s| Card::from_name(s.clone()))
-            .collect();
-
-        // Calculate and display probabilities for flop
-        println!("\nProbabilities with flop:");
-        let hand_probs =
-            calculate_hand_probabilities(&hole_cards, &community_cards, num_players, pack_count);
-        for (category, prob) in probability::get_ordered_probabilities(&hand_probs) {
-            println!("{}: {:.2}%", category, prob);
+        Err(e) => {
+            println!("Invalid input for cards: {}.", e);
+            return;
         }
+    };
+    println!("Your hole cards: {}", format_cards(&hole_cards));
+    let mut known_cards: Vec<Card> = hole_cards.clone();
 
-        let win_prob =
-            calculate_win_probability(&hole_cards, &community_cards, num_players, pack_count);
-        println!("Win probability: {:.2}%", win_prob);
-    }
-
-    // Ask for turn (1 card)
-    if cards_middle.len() == 3 {
-        let mut turn_input = String::new();
-        println!("\nEnter the turn (1 card) as a number (e.g., 6) or press Enter to skip:");
-        io::stdin().read_line(&mut turn_input).unwrap();
-        let turn_input = turn_input.trim();
-
-        if !turn_input.is_empty() {
-            let turn_number: usize = turn_input.parse().unwrap();
-            let turn_card = get_card_by_number(turn_number, &all_cards);
-            cards_middle.push(turn_card.clone());
-            println!("Turn: {:?}", cards_middle);
-
-            // Add turn card to community cards
-            community_cards.push(Card::from_name(turn_card));
+    // Calculate and display probabilities for hole cards
+    println!("\nProbabilities with hole cards:");
+    print_probabilities(&hole_cards, &[], num_players, pack_count);
 
-            // Calculate and display probabilities for turn
-            println!("\nProbabilities with turn:");
-            let hand_probs = calculate_hand_probabilities(
-                &hole_cards,
-                &community_cards,
-                num_players,
-                pack_count,
-            );
-            for (category, prob) in probability::get_ordered_probabilities(&hand_probs) {
-                println!("{}: {:.2}%", category, prob);
+    let mut community_cards: Vec<Card> = Vec::new();
+    let streets = [
+        (
+            "flop",
+            3,
+            "\nEnter the flop (3 cards, e.g. Ah Kd 7c) or press Enter to skip:",
+        ),
+        (
+            "turn",
+            1,
+            "\nEnter the turn (1 card, e.g. 2s) or press Enter to skip:",
+        ),
+        (
+            "river",
+            1,
+            "\nEnter the river (1 card, e.g. 9h) or press Enter to skip:",
+        ),
+    ];
+    for (street, count, prompt) in streets {
+        let cards = match read_street(prompt, count, &known_cards, pack_count) {
+            None => break,
+            Some(Ok(cards)) => cards,
+            Some(Err(e)) => {
+                println!("Invalid input for {}: {}.", street, e);
+                return;
             }
-
-            let win_prob =
-                calculate_win_probability(&hole_cards, &community_cards, num_players, pack_count);
-            println!("Win probability: {:.2}%", win_prob);
+        };
+        known_cards.extend(cards.iter().copied());
+        community_cards.extend(cards);
+        // A multi-pack board may repeat a card, which street notation can't show
+        match Board::from_cards(&community_cards) {
+            Ok(board) => println!("Board: {}", board),
+            Err(_) => println!("Board: {}", format_cards(&community_cards)),
         }
-    }
-
-    // Ask for river (1 card)
-    if cards_middle.len() == 4 {
-        let mut river_input = String::new();
-        println!("\nEnter the river (1 card) as a number (e.g., 7) or press Enter to skip:");
-        io::stdin().read_line(&mut river_input).unwrap();
-        let river_input = river_input.trim();
-
-        if !river_input.is_empty() {
-            let river_number: usize = river_input.parse().unwrap();
-            let river_card = get_card_by_number(river_number, &all_cards);
-            cards_middle.push(river_card.clone());
-            println!("River: {:?}", cards_middle);
-
-            // Add river card to community cards
-            community_cards.push(Card::from_name(river_card));
-
-            // Calculate and display probabilities for river
-            println!("\nProbabilities with river:");
-            let hand_probs = calculate_hand_probabilities(
-                &hole_cards,
-                &community_cards,
-                num_players,
-                pack_count,
-            );
-            for (category, prob) in probability::get_ordered_probabilities(&hand_probs) {
-                println!("{}: {:.2}%", category, prob);
-            }
+        println!(
+            "Current hand: {}",
+            describe_current_hand(&hole_cards, &community_cards)
+        );
 
-            let win_prob =
-                calculate_win_probability(&hole_cards, &community_cards, num_players, pack_count);
-            println!("Win probability: {:.2}%", win_prob);
+        println!("\nProbabilities with {}:", street);
+        print_probabilities(&hole_cards, &community_cards, num_players, pack_count);
+        if community_cards.len() < 5 && pack_count == 1 {
+            print_draws(&hole_cards, &community_cards);
+            print_outs(&hole_cards, &community_cards, num_players);
         }
     }
 
     // Combine the cards into a single vector
-    let mut all_cards: Vec<&str> = Vec::new();
-    all_cards.extend(cards.iter().map(|s| s.as_str()));
-    all_cards.extend(cards_middle.iter().map(|s| s.as_str()));
+    let mut all_cards: Vec<Card> = hole_cards.clone();
+    all_cards.extend(community_cards.iter().copied());
 
-    println!("All cards: {:?}", all_cards);
+    println!("All cards: {}", format_cards(&all_cards));
 
     // Only evaluate if we have at least 5 cards (2 hole cards + 3 community cards)
     if all_cards.len() >= 5 {
-        let rank = get_rank_of_7_perfect(all_cards);
+        let rank = evaluate(&all_cards);
         let rank_category = describe_rank_category(get_rank_category(rank));
-        // Convert rank to percentile (7462 is total number of distinct hands)
-        let percentile = ((7462 - rank) as f64 / 7462.0 * 100.0).round() as i32;
+        // Percentile among the holdings this board leaves possible
+        let strength = relative_strength(&hole_cards, &community_cards, pack_count);
+        println!(
+            "Your hand {} (rank {}) beats {}, ties {} and loses to {} of {} possible holdings: percentile {:.1} on this board",
+            rank_category,
+            rank,
+            strength.beats,
+            strength.ties,
+            strength.loses,
+            strength.total(),
+            strength.percentile()
+        );
+        if strength.is_nuts() {
+            println!("You hold the nuts");
+        } else {
+            println!(
+                "You hold the {}; the nuts is {} (e.g. {} {})",
+                strength.position_name(),
+                describe_rank_category(strength.nuts_category()),
+                strength.nuts[0],
+                strength.nuts[1]
+            );
+        }
         println!(
-            "Your hand {} is in the {}th percentile (rank {})",
-            rank_category, percentile, rank
+            "Best five cards: {}",
+            best_hand(&hole_cards, &community_cards)
         );
     } else {
-        println!("Not enough cards to evaluate a hand. Need at least 5 cards (2 hole cards + 3 community cards).");
+        println!(
+            "Not enough cards to evaluate a hand. Need at least 5 cards (2 hole cards + 3 community cards)."
+        );
     }
 }
 fn main() {
-    // let mut game = Game::new(5, 1000.0);
-    calculatePersonalHand();
+    // let mut game = Game::new(5, Blinds::new(5, 10));
+    calculate_personal_hand();
 }
@@ -0,0 +1,169 @@
+use crate::evaluator7::{evaluate_7cards, BINARIES_BY_ID};
+use crate::hash::hash_quinary;
+use crate::hash_table5::NOFLUSH5;
+use crate::hash_table6::NOFLUSH6;
+use crate::hash_table7::NOFLUSH7;
+use crate::hashtable::FLUSH;
+
+// Evaluation for shoes of several packs, where the same card can be dealt
+// more than once. Two rules extend the single-pack ranking:
+//
+// - Five cards of one rank make five of a kind, which beats a royal flush.
+//   These hands take the ranks -12 (five aces) to 0 (five deuces) so every
+//   other hand keeps its usual 1 to 7462 value.
+// - A flush needs five *different* cards of one suit: identical cards from
+//   different packs count once towards it.
+
+/// Rank of five of a kind of the given rank index (0 = Two .. 12 = Ace).
+pub const fn five_of_a_kind_rank(rank_index: usize) -> i32 {
+    -(rank_index as i32)
+}
+
+/// Evaluates 5 to 7 card ids that may contain duplicates. Agrees with
+/// `evaluate_5cards`, `evaluate_6cards` and `evaluate_7cards` whenever the
+/// cards are distinct.
+///
+/// # Panics
+///
+/// Panics if `ids` holds fewer than 5 or more than 7 cards.
+pub fn evaluate_multideck(ids: &[i32]) -> i32 {
+    assert!(
+        (5..=7).contains(&ids.len()),
+        "can only evaluate 5 to 7 cards, got {}",
+        ids.len()
+    );
+
+    let mut quinary: [u8; 13] = [0; 13];
+    let mut suit_binary: [i32; 4] = [0; 4];
+    for &id in ids {
+        quinary[(id >> 2) as usize] += 1;
+        suit_binary[(id & 0x3) as usize] |= BINARIES_BY_ID[id as usize];
+    }
+
+    // Seven cards can't hold five of a kind alongside anything better.
+    if let Some(rank_index) = (0..13).rev().find(|&i| quinary[i] >= 5) {
+        return five_of_a_kind_rank(rank_index);
+    }
+
+    let mut best = match ids.len() {
+        5 => NOFLUSH5[hash_quinary(&quinary, 5) as usize],
+        6 => NOFLUSH6[hash_quinary(&quinary, 6) as usize],
+        _ => NOFLUSH7[hash_quinary(&quinary, 7) as usize],
+    };
+    for binary in suit_binary {
+        if binary.count_ones() >= 5 {
+            best = best.min(FLUSH[binary as usize]);
+        }
+    }
+    best
+}
+
+/// Seven-card entry point with the signature of `evaluate_7cards`.
+pub fn evaluate_7cards_multideck(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32) -> i32 {
+    evaluate_multideck(&[a, b, c, d, e, f, g])
+}
+
+/// The seven-card evaluator for a shoe of `pack_count` packs: the table
+/// lookup for a single pack, the duplicate-aware one otherwise.
+pub fn seven_card_evaluator(pack_count: usize) -> fn(i32, i32, i32, i32, i32, i32, i32) -> i32 {
+    if pack_count > 1 {
+        evaluate_7cards_multideck
+    } else {
+        evaluate_7cards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Card;
+    use crate::evaluator::evaluate;
+    use crate::evaluator5::evaluate_5cards;
+    use crate::evaluator6::evaluate_6cards;
+    use crate::rank::{get_rank_category, RankCategory};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn ids(names: &[&str]) -> Vec<i32> {
+        names
+            .iter()
+            .map(|name| Card::from_name(name).unwrap().int32())
+            .collect()
+    }
+
+    #[test]
+    fn five_of_a_kind_beats_a_royal_flush() {
+        let royal = evaluate_multideck(&ids(&["Ah", "Kh", "Qh", "Jh", "Th"]));
+        assert_eq!(royal, 1);
+        let five_aces = evaluate_multideck(&ids(&["Ah", "Ah", "As", "Ad", "Ac"]));
+        let five_deuces = evaluate_multideck(&ids(&["2h", "2h", "2s", "2d", "2c", "Kh", "Qh"]));
+        assert_eq!(five_aces, five_of_a_kind_rank(12));
+        assert_eq!(five_aces, -12);
+        assert_eq!(five_deuces, 0);
+        assert!(five_aces < five_deuces && five_deuces < royal);
+        assert_eq!(get_rank_category(five_deuces), RankCategory::FiveOfAKind);
+        // Even with a straight flush among the seven cards
+        let both = ids(&["9h", "9h", "9s", "9d", "9c", "Th", "Jh"]);
+        assert_eq!(evaluate_multideck(&both), five_of_a_kind_rank(7));
+    }
+
+    #[test]
+    fn a_repeated_card_counts_once_towards_a_flush() {
+        // Four different hearts and a copy: a pair of jacks, not a flush
+        let doubled = evaluate_multideck(&ids(&["Ah", "Kh", "Qh", "Jh", "Jh"]));
+        assert_eq!(get_rank_category(doubled), RankCategory::OnePair);
+        let [a, b, c, d, e] = ids(&["Ah", "Kh", "Qh", "Jh", "Jc"]).try_into().unwrap();
+        assert_eq!(doubled, evaluate_5cards(a, b, c, d, e));
+        let seven = ids(&["Ah", "Kh", "9h", "4h", "4h", "2c", "7d"]);
+        assert_eq!(
+            get_rank_category(evaluate_multideck(&seven)),
+            RankCategory::OnePair
+        );
+        // Five different hearts still make one
+        let flush = ids(&["Ah", "Kh", "9h", "4h", "4h", "2h", "7d"]);
+        assert_eq!(
+            get_rank_category(evaluate_multideck(&flush)),
+            RankCategory::Flush
+        );
+    }
+
+    #[test]
+    fn one_pack_agrees_with_the_table_lookups() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let mut deck: Vec<i32> = (0..52).collect();
+        for _ in 0..5_000 {
+            deck.shuffle(&mut rng);
+            let [a, b, c, d, e, f, g] = deck[..7].try_into().unwrap();
+            let expected = evaluate_7cards(a, b, c, d, e, f, g);
+            assert_eq!(evaluate_7cards_multideck(a, b, c, d, e, f, g), expected);
+            assert_eq!(seven_card_evaluator(1)(a, b, c, d, e, f, g), expected);
+            assert_eq!(seven_card_evaluator(2)(a, b, c, d, e, f, g), expected);
+            assert_eq!(
+                evaluate_multideck(&deck[..6]),
+                evaluate_6cards(a, b, c, d, e, f)
+            );
+            assert_eq!(
+                evaluate_multideck(&deck[..5]),
+                evaluate_5cards(a, b, c, d, e)
+            );
+        }
+    }
+
+    #[test]
+    fn evaluate_dispatches_repeated_cards_here() {
+        let cards: Vec<Card> = ["Ah", "Ah", "As", "Ad", "Ac", "2c", "3d"]
+            .iter()
+            .map(|name| Card::from_name(name).unwrap())
+            .collect();
+        assert_eq!(evaluate(&cards), -12);
+        let ace = cards[0];
+        let doubled = [ace, ace, cards[5], cards[6], Card::from_name("Kh").unwrap()];
+        assert_eq!(get_rank_category(evaluate(&doubled)), RankCategory::OnePair);
+        // Only the multi-pack evaluator is safe with repeats
+        let [a, b, c, d, e, f, g] = ids(&["Ah", "Ah", "As", "Ad", "Ac", "2c", "3d"])
+            .try_into()
+            .unwrap();
+        assert_eq!(seven_card_evaluator(2)(a, b, c, d, e, f, g), -12);
+    }
+}
@@ -0,0 +1,416 @@
+use crate::card::{Card, CardParseError};
+use crate::card_set::CardSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a hand or board string could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    /// A token could not be read as a card.
+    InvalidCard {
+        token: String,
+        source: CardParseError,
+    },
+    /// The same card appears more than once.
+    DuplicateCard(Card),
+    /// A `[` without its `]`, a stray `]`, or nested brackets.
+    UnbalancedBrackets,
+    /// The input holds a different number of cards than the context requires.
+    WrongCardCount {
+        expected: &'static str,
+        found: usize,
+    },
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::InvalidCard { token, source } => {
+                write!(f, "invalid card \"{}\": {}", token, source)
+            }
+            NotationError::DuplicateCard(card) => write!(f, "card {} appears more than once", card),
+            NotationError::UnbalancedBrackets => write!(f, "unbalanced street brackets"),
+            NotationError::WrongCardCount { expected, found } => {
+                write!(f, "expected {} cards, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NotationError::InvalidCard { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a list of cards written either concatenated ("AhKsQd"), separated by
+/// spaces and/or commas ("Ah Kd, 7c") or any mix of the two. Brackets are
+/// ignored, so "[Ah Kd 7c] [2s]" reads as four cards. Duplicates are rejected.
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, NotationError> {
+    parse_cards_from_packs(input, 1)
+}
+
+/// Like [`parse_cards`], for a shoe of `pack_count` packs: a card may appear
+/// up to `pack_count` times.
+pub fn parse_cards_from_packs(input: &str, pack_count: usize) -> Result<Vec<Card>, NotationError> {
+    let mut copies = [0; 52];
+    let mut cards = Vec::new();
+    for group in split_groups(input)? {
+        for card in parse_group(group)? {
+            copies[card.int()] += 1;
+            if copies[card.int()] > pack_count {
+                return Err(NotationError::DuplicateCard(card));
+            }
+            cards.push(card);
+        }
+    }
+    Ok(cards)
+}
+
+/// Reads exactly two hole cards, e.g. "AhKs" or "Ah Ks".
+pub fn parse_hole_cards(input: &str) -> Result<[Card; 2], NotationError> {
+    let cards = parse_cards(input)?;
+    match cards[..] {
+        [a, b] => Ok([a, b]),
+        _ => Err(NotationError::WrongCardCount {
+            expected: "2",
+            found: cards.len(),
+        }),
+    }
+}
+
+/// Community cards split by street.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Board {
+    pub flop: Option<[Card; 3]>,
+    pub turn: Option<Card>,
+    pub river: Option<Card>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Board {
+    /// Builds a board from 0, 3, 4 or 5 distinct cards dealt in order.
+    pub fn from_cards(cards: &[Card]) -> Result<Board, NotationError> {
+        let mut seen = CardSet::new();
+        for &card in cards {
+            if !seen.insert(card) {
+                return Err(NotationError::DuplicateCard(card));
+            }
+        }
+        match *cards {
+            [] => Ok(Board::default()),
+            [a, b, c] => Ok(Board {
+                flop: Some([a, b, c]),
+                ..Board::default()
+            }),
+            [a, b, c, d] => Ok(Board {
+                flop: Some([a, b, c]),
+                turn: Some(d),
+                river: None,
+            }),
+            [a, b, c, d, e] => Ok(Board {
+                flop: Some([a, b, c]),
+                turn: Some(d),
+                river: Some(e),
+            }),
+            _ => Err(NotationError::WrongCardCount {
+                expected: "0, 3, 4 or 5",
+                found: cards.len(),
+            }),
+        }
+    }
+
+    pub fn street(&self) -> Street {
+        if self.river.is_some() {
+            Street::River
+        } else if self.turn.is_some() {
+            Street::Turn
+        } else if self.flop.is_some() {
+            Street::Flop
+        } else {
+            Street::Preflop
+        }
+    }
+
+    /// All community cards in dealing order.
+    pub fn cards(&self) -> Vec<Card> {
+        let mut cards = Vec::with_capacity(5);
+        if let Some(flop) = self.flop {
+            cards.extend(flop);
+        }
+        cards.extend(self.turn);
+        cards.extend(self.river);
+        cards
+    }
+
+    pub fn card_set(&self) -> CardSet {
+        self.cards().into_iter().collect()
+    }
+}
+
+impl FromStr for Board {
+    type Err = NotationError;
+
+    /// Accepts a flat list ("Ah Kd 7c 2s") or PokerStars-style street groups
+    /// ("[Ah Kd 7c] [2s]" or "[Ah Kd 7c 2s] [3d]"). With brackets, every
+    /// group has to end on a street boundary.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seen = CardSet::new();
+        let mut cards = Vec::new();
+        let groups = split_groups(s)?;
+        let bracketed = groups.len() > 1 || s.contains('[');
+        for group in groups {
+            for card in parse_group(group)? {
+                if !seen.insert(card) {
+                    return Err(NotationError::DuplicateCard(card));
+                }
+                cards.push(card);
+            }
+            if bracketed && !matches!(cards.len(), 3..=5) {
+                return Err(NotationError::WrongCardCount {
+                    expected: "3, 4 or 5",
+                    found: cards.len(),
+                });
+            }
+        }
+        Board::from_cards(&cards)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some([a, b, c]) = self.flop {
+            write!(f, "[{} {} {}]", a, b, c)?;
+        }
+        if let Some(turn) = self.turn {
+            write!(f, " [{}]", turn)?;
+        }
+        if let Some(river) = self.river {
+            write!(f, " [{}]", river)?;
+        }
+        Ok(())
+    }
+}
+
+// Splits the input on top-level brackets. Text outside brackets forms its own
+// group so "Ah Kd 7c" is a single group.
+fn split_groups(input: &str) -> Result<Vec<&str>, NotationError> {
+    let mut groups = Vec::new();
+    let mut rest = input;
+    while let Some(open) = rest.find(['[', ']']) {
+        if rest.as_bytes()[open] == b']' {
+            return Err(NotationError::UnbalancedBrackets);
+        }
+        push_group(&mut groups, &rest[..open]);
+        let inner = &rest[open + 1..];
+        let close = inner.find(']').ok_or(NotationError::UnbalancedBrackets)?;
+        if inner[..close].contains('[') {
+            return Err(NotationError::UnbalancedBrackets);
+        }
+        push_group(&mut groups, &inner[..close]);
+        rest = &inner[close + 1..];
+    }
+    push_group(&mut groups, rest);
+    Ok(groups)
+}
+
+fn push_group<'a>(groups: &mut Vec<&'a str>, group: &'a str) {
+    if !group.trim().is_empty() {
+        groups.push(group);
+    }
+}
+
+fn parse_group(group: &str) -> Result<Vec<Card>, NotationError> {
+    let mut cards = Vec::new();
+    for token in group
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+    {
+        let chars: Vec<char> = token.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err(NotationError::InvalidCard {
+                token: token.to_string(),
+                source: CardParseError::InvalidLength(chars.len()),
+            });
+        }
+        for pair in chars.chunks(2) {
+            let name: String = pair.iter().collect();
+            let card = Card::from_name(&name).map_err(|source| NotationError::InvalidCard {
+                token: name.clone(),
+                source,
+            })?;
+            cards.push(card);
+        }
+    }
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(names: &[&str]) -> Vec<Card> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn card_lists_in_every_layout() {
+        let expected = cards(&["Ah", "Kd", "7c", "2s"]);
+        for input in [
+            "AhKd7c2s",
+            "Ah Kd 7c 2s",
+            "Ah, Kd, 7c, 2s",
+            "AhKd 7c,2s",
+            "[Ah Kd 7c] [2s]",
+            "[AhKd7c][2s]",
+        ] {
+            assert_eq!(parse_cards(input), Ok(expected.clone()), "{}", input);
+        }
+        assert_eq!(parse_cards(""), Ok(Vec::new()));
+        assert_eq!(
+            parse_hole_cards("AhKs"),
+            Ok([
+                Card::from_name("Ah").unwrap(),
+                Card::from_name("Ks").unwrap()
+            ])
+        );
+        assert_eq!(
+            parse_hole_cards("Ah Ks Qd"),
+            Err(NotationError::WrongCardCount {
+                expected: "2",
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn bad_cards_name_their_token() {
+        assert_eq!(
+            parse_cards("Ah Kx"),
+            Err(NotationError::InvalidCard {
+                token: "Kx".to_string(),
+                source: CardParseError::UnknownSuit('x'),
+            })
+        );
+        assert_eq!(
+            parse_cards("Ah Kd7"),
+            Err(NotationError::InvalidCard {
+                token: "Kd7".to_string(),
+                source: CardParseError::InvalidLength(3),
+            })
+        );
+    }
+
+    #[test]
+    fn unbalanced_brackets() {
+        for input in ["[Ah Kd 7c", "Ah Kd 7c]", "[Ah [Kd] 7c]", "]Ah Kd 7c["] {
+            assert_eq!(
+                parse_cards(input),
+                Err(NotationError::UnbalancedBrackets),
+                "{}",
+                input
+            );
+            assert_eq!(
+                input.parse::<Board>(),
+                Err(NotationError::UnbalancedBrackets),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn duplicates_are_refused_unless_there_are_packs_for_them() {
+        let ace = Card::from_name("Ah").unwrap();
+        assert_eq!(
+            parse_cards("Ah Kd Ah"),
+            Err(NotationError::DuplicateCard(ace))
+        );
+        assert_eq!(
+            parse_cards("[Ah Kd 7c] [Ah]"),
+            Err(NotationError::DuplicateCard(ace))
+        );
+        assert_eq!(
+            parse_cards_from_packs("Ah Kd Ah", 2),
+            Ok(cards(&["Ah", "Kd", "Ah"]))
+        );
+        assert_eq!(
+            parse_cards_from_packs("Ah Ah Ah", 2),
+            Err(NotationError::DuplicateCard(ace))
+        );
+        assert_eq!(
+            "Ah Kd 7c Ah".parse::<Board>(),
+            Err(NotationError::DuplicateCard(ace))
+        );
+    }
+
+    #[test]
+    fn boards_by_street() {
+        let flop: Board = "Ah Kd 7c".parse().unwrap();
+        assert_eq!(flop.street(), Street::Flop);
+        assert_eq!(flop.cards(), cards(&["Ah", "Kd", "7c"]));
+        assert_eq!((flop.turn, flop.river), (None, None));
+        let turn: Board = "[Ah Kd 7c] [2s]".parse().unwrap();
+        assert_eq!(turn.street(), Street::Turn);
+        assert_eq!(turn, "Ah Kd 7c 2s".parse().unwrap());
+        // The turn and river may also share a group with the cards before
+        let river: Board = "[Ah Kd 7c 2s] [3d]".parse().unwrap();
+        assert_eq!(river.street(), Street::River);
+        assert_eq!(river.cards(), cards(&["Ah", "Kd", "7c", "2s", "3d"]));
+        assert_eq!(river.card_set().len(), 5);
+        assert_eq!("".parse::<Board>().unwrap().street(), Street::Preflop);
+    }
+
+    #[test]
+    fn wrong_street_sizes() {
+        assert_eq!(
+            "[Ah Kd] 7c".parse::<Board>(),
+            Err(NotationError::WrongCardCount {
+                expected: "3, 4 or 5",
+                found: 2
+            })
+        );
+        assert_eq!(
+            "[Ah Kd 7c] [2s 3d 4h]".parse::<Board>(),
+            Err(NotationError::WrongCardCount {
+                expected: "3, 4 or 5",
+                found: 6
+            })
+        );
+        assert_eq!(
+            "Ah Kd".parse::<Board>(),
+            Err(NotationError::WrongCardCount {
+                expected: "0, 3, 4 or 5",
+                found: 2
+            })
+        );
+        assert_eq!(
+            Board::from_cards(&cards(&["Ah", "Kd", "7c", "2s", "3d", "4h"])),
+            Err(NotationError::WrongCardCount {
+                expected: "0, 3, 4 or 5",
+                found: 6
+            })
+        );
+    }
+
+    #[test]
+    fn boards_round_trip_through_display() {
+        for input in ["[Ah Kd 7c]", "[Ah Kd 7c] [2s]", "[Ah Kd 7c] [2s] [3d]", ""] {
+            let board: Board = input.parse().unwrap();
+            assert_eq!(board.to_string(), input);
+            assert_eq!(board.to_string().parse::<Board>(), Ok(board));
+        }
+        let flat: Board = "Ah Kd 7c 2s 3d".parse().unwrap();
+        assert_eq!(flat.to_string(), "[Ah Kd 7c] [2s] [3d]");
+    }
+}
@@ -0,0 +1,287 @@
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::enumeration::choose;
+use crate::evaluator::evaluate_hand;
+use crate::hand::Hand;
+use crate::range::Range;
+use crate::rank::{get_rank_category, RankCategory};
+use std::cmp::Reverse;
+
+// An out is an unseen card that improves our hand category past both what we
+// hold now and what the board alone makes, and leaves us ahead once it's
+// dealt. "Ahead" compares made hands on the next street: against each
+// opponent we take the share of their range we beat (ties counting half), and
+// multiply the shares over opponents.
+// An out is tainted when it also lifts some of an opponent's range past us,
+// like a flush card that pairs the board and fills up a set.
+
+/// One card that improves us to a winning hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Out {
+    pub card: Card,
+    /// Our hand category once the card is dealt.
+    pub category: RankCategory,
+    /// Chance that our hand beats every opponent's after the card, ties
+    /// counting half.
+    pub showdown_share: f64,
+    /// The largest share of one opponent's range that the card improves to a
+    /// hand beating ours; 0 for a clean out.
+    pub tainted_share: f64,
+}
+
+impl Out {
+    pub fn is_clean(&self) -> bool {
+        self.tainted_share == 0.0
+    }
+}
+
+/// The outs of a flop or turn spot, highest card first in each list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outs {
+    pub clean: Vec<Out>,
+    pub tainted: Vec<Out>,
+    /// Cards still to come: 2 on the flop, 1 on the turn.
+    pub cards_to_come: usize,
+    /// Cards neither in our hand nor on the board.
+    pub unseen: usize,
+}
+
+impl Outs {
+    pub fn count(&self) -> usize {
+        self.clean.len() + self.tainted.len()
+    }
+
+    /// All outs, highest card first.
+    pub fn all(&self) -> Vec<Out> {
+        let mut all: Vec<Out> = self.clean.iter().chain(&self.tainted).copied().collect();
+        all.sort_by_key(|out| Reverse(out.card));
+        all
+    }
+
+    /// The rule of 4 on the flop and of 2 on the turn, in percent.
+    pub fn rule_of_thumb(&self) -> f64 {
+        (self.count() * 2 * self.cards_to_come).min(100) as f64
+    }
+
+    /// Exact chance, in percent, that at least one out comes by the river.
+    pub fn hit_probability(&self) -> f64 {
+        let all = choose(self.unseen, self.cards_to_come);
+        if all == 0 {
+            return 0.0;
+        }
+        let misses = choose(self.unseen - self.count(), self.cards_to_come);
+        (1.0 - misses as f64 / all as f64) * 100.0
+    }
+}
+
+/// Lists the unseen cards that improve `hole_cards` to a winning hand on a
+/// flop or turn `board`, against one opponent per range. Pass
+/// `Range::random()` for an opponent who may hold anything. Assumes a single
+/// pack.
+///
+/// # Panics
+///
+/// Panics unless there are 2 hole cards and 3 or 4 board cards.
+pub fn outs(hole_cards: &[Card], board: &[Card], opponents: &[Range]) -> Outs {
+    assert_eq!(hole_cards.len(), 2, "need 2 hole cards");
+    assert!(
+        (3..=4).contains(&board.len()),
+        "outs are counted on the flop or turn, got {} board cards",
+        board.len()
+    );
+
+    let dead: CardSet = hole_cards.iter().chain(board).collect();
+    let board_hand = Hand::from_cards(board);
+    let our_rank =
+        |board: &Hand| evaluate_hand(&board.with_hole_cards(hole_cards[0], hole_cards[1])).value();
+    let current_category = get_rank_category(our_rank(&board_hand)) as usize;
+
+    // Every opponent's holdings with their current rank
+    let opponents: Vec<Vec<([Card; 2], f64, i32)>> = opponents
+        .iter()
+        .map(|range| {
+            range
+                .without(dead)
+                .combos()
+                .iter()
+                .map(|&(combo, weight)| {
+                    let hand = board_hand.with_hole_cards(combo[0], combo[1]);
+                    (combo, weight, evaluate_hand(&hand).value())
+                })
+                .collect()
+        })
+        .collect();
+
+    let unseen: Vec<Card> = dead.complement().iter().collect();
+    let mut outs = Outs {
+        clean: Vec::new(),
+        tainted: Vec::new(),
+        cards_to_come: 5 - board.len(),
+        unseen: unseen.len(),
+    };
+    for &card in unseen.iter().rev() {
+        let mut next_board = board_hand;
+        next_board.add_card(card);
+        let rank = our_rank(&next_board);
+        let category = get_rank_category(rank);
+        // A card that helps everyone, like one pairing the board, is no out
+        if category as usize >= current_category.min(board_category(&next_board)) {
+            continue;
+        }
+
+        let mut showdown_share = 1.0;
+        let mut tainted_share: f64 = 0.0;
+        for holdings in &opponents {
+            let (mut total, mut beaten, mut tied, mut lifted) = (0.0, 0.0, 0.0, 0.0);
+            for &(combo, weight, before) in holdings {
+                if combo.contains(&card) {
+                    continue;
+                }
+                let after = evaluate_hand(&next_board.with_hole_cards(combo[0], combo[1])).value();
+                total += weight;
+                if after > rank {
+                    beaten += weight;
+                } else if after == rank {
+                    tied += weight;
+                } else if (get_rank_category(after) as usize) < get_rank_category(before) as usize {
+                    lifted += weight;
+                }
+            }
+            if total > 0.0 {
+                showdown_share *= (beaten + tied / 2.0) / total;
+                tainted_share = tainted_share.max(lifted / total);
+            }
+        }
+        if showdown_share < 0.5 {
+            continue;
+        }
+
+        let out = Out {
+            card,
+            category,
+            showdown_share,
+            tainted_share,
+        };
+        if out.is_clean() {
+            outs.clean.push(out);
+        } else {
+            outs.tainted.push(out);
+        }
+    }
+    outs
+}
+
+// Category every player holds at least with a 4 or 5 card board, as an index
+// into `RankCategory::ALL`.
+fn board_category(board: &Hand) -> usize {
+    if *board.size() == 5 {
+        return get_rank_category(evaluate_hand(board).value()) as usize;
+    }
+    let mut counts: Vec<u8> = board.get_quinary().to_vec();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let category = match counts[..2] {
+        [4, _] => RankCategory::FourOfAKind,
+        [3, _] => RankCategory::ThreeOfAKind,
+        [2, 2] => RankCategory::TwoPair,
+        [2, _] => RankCategory::OnePair,
+        _ => RankCategory::HighCard,
+    };
+    category as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardRank;
+    use crate::notation::parse_cards;
+
+    fn spot(hole: &str, board: &str, opponents: &[&str]) -> Outs {
+        let ranges: Vec<Range> = opponents
+            .iter()
+            .map(|range| range.parse().unwrap())
+            .collect();
+        outs(
+            &parse_cards(hole).unwrap(),
+            &parse_cards(board).unwrap(),
+            &ranges,
+        )
+    }
+
+    fn cards(outs: &[Out]) -> Vec<Card> {
+        outs.iter().map(|out| out.card).collect()
+    }
+
+    #[test]
+    fn the_nut_flush_draw_has_nine_outs() {
+        // Against two pair, only the flush gets there
+        let outs = spot("Ah Kh", "Qh 7h 2c", &["Qc7c"]);
+        assert_eq!(outs.count(), 9);
+        assert!(outs.tainted.is_empty());
+        assert_eq!(
+            cards(&outs.clean),
+            parse_cards("Jh Th 9h 8h 6h 5h 4h 3h 2h").unwrap()
+        );
+        assert!(outs
+            .clean
+            .iter()
+            .all(|out| out.category == RankCategory::Flush));
+        assert_eq!(outs.cards_to_come, 2);
+        assert_eq!(outs.unseen, 47);
+        assert_eq!(outs.rule_of_thumb(), 36.0);
+        // 1 - C(38, 2) / C(47, 2)
+        assert!((outs.hit_probability() - 34.967_6).abs() < 1e-3);
+    }
+
+    #[test]
+    fn straight_draws_against_an_overpair() {
+        let open_ended = spot("9c 8d", "7h 6s 2c", &["AsAd"]);
+        assert_eq!(open_ended.count(), 8);
+        assert!(open_ended
+            .all()
+            .iter()
+            .all(|out| out.category == RankCategory::Straight));
+        assert!((open_ended.hit_probability() - 31.452_4).abs() < 1e-3);
+
+        let gutshot = spot("9c 8d", "Jh 7s 2c", &["AsAd"]);
+        assert_eq!(cards(&gutshot.all()), parse_cards("Ts Th Td Tc").unwrap());
+        // On the turn, the rule of 2
+        let turn = spot("9c 8d", "Jh 7s 2c 3d", &["AsAd"]);
+        assert_eq!(turn.count(), 4);
+        assert_eq!(turn.cards_to_come, 1);
+        assert_eq!(turn.rule_of_thumb(), 8.0);
+        assert!((turn.hit_probability() - 100.0 * 4.0 / 46.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_flush_card_pairing_the_board_is_tainted() {
+        // 3h makes our flush but fills up the set of sevens
+        let outs = spot("Ah Kh", "Qh 7h 3c", &["7s7d:0.5, QcJc"]);
+        assert_eq!(cards(&outs.tainted), parse_cards("3h").unwrap());
+        let tainted = outs.tainted[0];
+        assert!((tainted.tainted_share - 1.0 / 3.0).abs() < 1e-9);
+        assert!((tainted.showdown_share - 2.0 / 3.0).abs() < 1e-9);
+        // The other hearts, and an ace or king against the queen
+        assert_eq!(outs.clean.len(), 14);
+        assert!(outs.clean.iter().all(|out| out.showdown_share == 1.0
+            || (out.category == RankCategory::OnePair
+                && (out.showdown_share - 2.0 / 3.0).abs() < 1e-9)));
+    }
+
+    #[test]
+    fn cards_that_help_the_board_are_no_outs() {
+        // Pairing the board gives everyone the same pair
+        let outs = spot("Ah Kd", "9c 7s 2h", &["random"]);
+        assert_eq!(
+            cards(&outs.all()),
+            parse_cards("As Ad Ac Ks Kh Kc").unwrap()
+        );
+        assert!(cards(&outs.all()).iter().all(|card| !matches!(
+            card.rank(),
+            CardRank::Nine | CardRank::Seven | CardRank::Two
+        )));
+        // Nothing improves the nuts past what it is
+        let nuts = spot("Ah Kh", "Qh Jh Th", &["random"]);
+        assert_eq!(nuts.count(), 0);
+        assert_eq!(nuts.hit_probability(), 0.0);
+    }
+}
@@ -1,23 +1,26 @@
 use crate::card::Card;
 use std::fmt;
+#[derive(Default)]
 pub enum DecisionType {
     Fold,
     Check,
     Call,
+    Bet,
+    Raise,
+    AllIn,
+    #[default]
     Pending,
 }
 
-impl Default for DecisionType {
-    fn default() -> Self {
-        Self::Pending
-    }
-}
 impl fmt::Display for DecisionType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DecisionType::Fold => write!(f, "Fold"),
             DecisionType::Check => write!(f, "Check"),
             DecisionType::Call => write!(f, "Call"),
+            DecisionType::Bet => write!(f, "Bet"),
+            DecisionType::Raise => write!(f, "Raise"),
+            DecisionType::AllIn => write!(f, "All-in"),
             DecisionType::Pending => write!(f, "Pending"),
         }
     }
@@ -25,10 +28,10 @@ impl fmt::Display for DecisionType {
 #[derive(Default)]
 pub struct Decision {
     pub decision_type: DecisionType,
-    pub raise_amount: f32,
+    pub raise_amount: u32,
 }
 impl Decision {
-    pub fn new(dt: DecisionType, ra: f32) -> Decision {
+    pub fn new(dt: DecisionType, ra: u32) -> Decision {
         Decision {
             decision_type: dt,
             raise_amount: ra,
@@ -40,18 +43,24 @@ pub struct Player {
     pub nickname: String,
     pub cards: Vec<Card>,
     pub is_folded: bool,
-    pub balance: f32,
-    pub wagered: f32,
+    pub balance: u32,
+    pub wagered: u32,
     pub hand_rank: i32,
     pub decisions: Vec<Decision>,
 }
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Player {
     pub fn new() -> Player {
         Player {
             nickname: "Bing chilling".to_owned(),
             cards: Vec::new(),
-            balance: 5000.0,
-            wagered: 0.0,
+            balance: 5000,
+            wagered: 0,
             is_folded: false,
             hand_rank: 1,
             decisions: Vec::new(),
@@ -60,7 +69,7 @@ impl Player {
     pub fn add_card(&mut self, card: Card) {
         self.cards.push(card);
     }
-    pub fn check(&mut self, amount: f32) {
+    pub fn check(&mut self, amount: u32) {
         if amount > self.balance {
             println!("Insufficient balance!");
         } else {
@@ -73,7 +82,7 @@ impl Player {
         self.is_folded = true;
     }
 
-    pub fn wager(&mut self, amount: f32) {
+    pub fn wager(&mut self, amount: u32) {
         if amount > self.balance {
             println!("Insufficient balance!");
         } else {
@@ -82,15 +91,15 @@ impl Player {
         }
     }
 
-    pub fn increment_wager(&mut self, amount: f32) {
+    pub fn increment_wager(&mut self, amount: u32) {
         self.wagered += amount;
     }
 
-    pub fn decrement_balance(&mut self, amount: f32) {
+    pub fn decrement_balance(&mut self, amount: u32) {
         self.balance -= amount;
     }
 
-    pub fn increment_balance(&mut self, amount: f32) {
+    pub fn increment_balance(&mut self, amount: u32) {
         self.balance += amount;
     }
 }
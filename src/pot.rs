@@ -0,0 +1,315 @@
+use crate::player::Player;
+
+// Pots are cut from what each player put in over the hand. Every all-in level
+// of a player still in closes a pot: the main pot holds what everyone matched
+// up to the shortest all-in, each side pot what was matched above the level
+// before. Folded players' chips stay in the pots they reached but they can't
+// win any. A bet nobody matched ends up alone in the last pot, so it goes back
+// to its owner.
+//
+// Tied winners split a pot evenly. Chips that don't divide go one at a time to
+// the winners closest to the left of the button.
+
+/// One pot and the players who can win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u32,
+    /// Seats still in the hand that matched this pot's level, ascending.
+    pub eligible: Vec<usize>,
+}
+
+/// Builds the main pot, then each side pot, from every seat's chips put in
+/// over the hand.
+///
+/// # Panics
+///
+/// Panics if `contributions` and `folded` differ in length.
+pub fn build_pots(contributions: &[u32], folded: &[bool]) -> Vec<Pot> {
+    assert_eq!(
+        contributions.len(),
+        folded.len(),
+        "one contribution per seat"
+    );
+    let live = |seat: &usize| !folded[*seat];
+    let mut levels: Vec<u32> = (0..contributions.len())
+        .filter(live)
+        .map(|seat| contributions[seat])
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots: Vec<Pot> = Vec::new();
+    let mut previous = 0;
+    for level in levels {
+        let amount = contributions
+            .iter()
+            .map(|&put_in| put_in.min(level) - put_in.min(previous))
+            .sum();
+        let eligible = (0..contributions.len())
+            .filter(live)
+            .filter(|&seat| contributions[seat] >= level)
+            .collect();
+        if amount > 0 {
+            pots.push(Pot { amount, eligible });
+        }
+        previous = level;
+    }
+    // Folded chips above every live player's level
+    let rest: u32 = contributions
+        .iter()
+        .map(|&put_in| put_in.saturating_sub(previous))
+        .sum();
+    if rest > 0 {
+        match pots.last_mut() {
+            Some(pot) => pot.amount += rest,
+            None => pots.push(Pot {
+                amount: rest,
+                eligible: Vec::new(),
+            }),
+        }
+    }
+    pots
+}
+
+/// Chips each seat wins from `pots`, given every seat's hand rank (lower is
+/// better; ignored for seats not eligible). Odd chips go to tied winners in
+/// seat order from `first_odd_chip` on, usually the seat left of the button.
+/// A pot nobody is eligible for is not paid out.
+pub fn award_pots(pots: &[Pot], ranks: &[i32], first_odd_chip: usize) -> Vec<u32> {
+    let seats = ranks.len();
+    let mut won = vec![0; seats];
+    for pot in pots {
+        let Some(best) = pot.eligible.iter().map(|&seat| ranks[seat]).min() else {
+            continue;
+        };
+        let mut winners: Vec<usize> = pot
+            .eligible
+            .iter()
+            .copied()
+            .filter(|&seat| ranks[seat] == best)
+            .collect();
+        winners.sort_by_key(|&seat| (seat + seats - first_odd_chip % seats) % seats);
+        let share = pot.amount / winners.len() as u32;
+        let odd_chips = pot.amount as usize % winners.len();
+        for (i, &seat) in winners.iter().enumerate() {
+            won[seat] += share + u32::from(i < odd_chips);
+        }
+    }
+    won
+}
+
+/// Pays out a hand: builds the pots from each player's `wagered` chips,
+/// awards them by `ranks` and credits every winner's balance. Returns the
+/// chips each seat won.
+pub fn settle(players: &mut [Player], ranks: &[i32], first_odd_chip: usize) -> Vec<u32> {
+    let contributions: Vec<u32> = players.iter().map(|pl| pl.wagered).collect();
+    let folded: Vec<bool> = players.iter().map(|pl| pl.is_folded).collect();
+    let won = award_pots(&build_pots(&contributions, &folded), ranks, first_odd_chip);
+    for (pl, &chips) in players.iter_mut().zip(&won) {
+        pl.increment_balance(chips);
+    }
+    won
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pot(amount: u32, eligible: &[usize]) -> Pot {
+        Pot {
+            amount,
+            eligible: eligible.to_vec(),
+        }
+    }
+
+    // Chips won per seat when nobody folded
+    fn showdown(contributions: &[u32], ranks: &[i32], first_odd_chip: usize) -> Vec<u32> {
+        let folded = vec![false; contributions.len()];
+        award_pots(&build_pots(contributions, &folded), ranks, first_odd_chip)
+    }
+
+    #[test]
+    fn equal_stacks_make_one_pot() {
+        let pots = build_pots(&[100, 100, 100], &[false, false, false]);
+        assert_eq!(pots, vec![pot(300, &[0, 1, 2])]);
+        assert_eq!(
+            showdown(&[100, 100, 100], &[50, 10, 30], 0),
+            vec![0, 300, 0]
+        );
+    }
+
+    #[test]
+    fn three_way_all_in_with_different_stacks() {
+        let contributions = [100, 300, 500];
+        let pots = build_pots(&contributions, &[false; 3]);
+        assert_eq!(
+            pots,
+            vec![pot(300, &[0, 1, 2]), pot(400, &[1, 2]), pot(200, &[2])]
+        );
+        // Short stack best, then the middle stack: the big stack only gets
+        // back what nobody could match
+        assert_eq!(showdown(&contributions, &[1, 2, 3], 0), vec![300, 400, 200]);
+        // Big stack best takes everything
+        assert_eq!(showdown(&contributions, &[3, 2, 1], 0), vec![0, 0, 900]);
+        // Middle stack best wins the main and first side pot
+        assert_eq!(showdown(&contributions, &[3, 1, 2], 0), vec![0, 700, 200]);
+    }
+
+    #[test]
+    fn four_way_all_in_with_a_tie_in_a_side_pot() {
+        let contributions = [50, 200, 200, 400];
+        let pots = build_pots(&contributions, &[false; 4]);
+        assert_eq!(
+            pots,
+            vec![
+                pot(200, &[0, 1, 2, 3]),
+                pot(450, &[1, 2, 3]),
+                pot(200, &[3])
+            ]
+        );
+        // Seat 0 wins the main pot, seats 1 and 2 chop the side pot
+        assert_eq!(
+            showdown(&contributions, &[1, 5, 5, 9], 0),
+            vec![200, 225, 225, 200]
+        );
+    }
+
+    #[test]
+    fn all_ins_at_the_same_level_share_a_pot() {
+        let pots = build_pots(&[80, 80, 200, 200], &[false; 4]);
+        assert_eq!(pots, vec![pot(320, &[0, 1, 2, 3]), pot(240, &[2, 3])]);
+    }
+
+    #[test]
+    fn folded_chips_stay_in_the_pots_they_reached() {
+        // Seat 1 called 150 then folded to seat 2's all-in
+        let contributions = [100, 150, 400, 400];
+        let folded = [false, true, false, false];
+        let pots = build_pots(&contributions, &folded);
+        assert_eq!(pots, vec![pot(400, &[0, 2, 3]), pot(650, &[2, 3])]);
+        // The folded seat holds the best hand but wins nothing
+        let won = award_pots(&pots, &[2, 1, 3, 4], 0);
+        assert_eq!(won, vec![400, 0, 650, 0]);
+    }
+
+    #[test]
+    fn folded_chips_above_an_all_in_join_the_side_pot() {
+        // Seat 0 bet 300 and folded to a raise, seat 1 is all-in for 100
+        let contributions = [300, 100, 500];
+        let folded = [true, false, false];
+        let pots = build_pots(&contributions, &folded);
+        assert_eq!(pots, vec![pot(300, &[1, 2]), pot(600, &[2])]);
+    }
+
+    #[test]
+    fn folded_chips_above_every_live_level_join_the_last_pot() {
+        let contributions = [400, 100, 200];
+        let folded = [true, false, false];
+        let pots = build_pots(&contributions, &folded);
+        assert_eq!(pots, vec![pot(300, &[1, 2]), pot(400, &[2])]);
+    }
+
+    #[test]
+    fn an_uncalled_bet_goes_back() {
+        let contributions = [100, 250];
+        let pots = build_pots(&contributions, &[false, false]);
+        assert_eq!(pots, vec![pot(200, &[0, 1]), pot(150, &[1])]);
+        assert_eq!(showdown(&contributions, &[1, 2], 0), vec![200, 150]);
+    }
+
+    #[test]
+    fn everyone_folding_leaves_one_winner() {
+        let contributions = [15, 5, 10, 0];
+        let folded = [false, true, true, true];
+        let pots = build_pots(&contributions, &folded);
+        assert_eq!(pots, vec![pot(30, &[0])]);
+        assert_eq!(award_pots(&pots, &[7462; 4], 0), vec![30, 0, 0, 0]);
+    }
+
+    #[test]
+    fn a_tie_splits_evenly() {
+        assert_eq!(
+            showdown(&[100, 100, 100, 100], &[2, 2, 2, 2], 0),
+            vec![100, 100, 100, 100]
+        );
+    }
+
+    #[test]
+    fn odd_chips_go_left_of_the_button() {
+        // 101 chips between two tied winners
+        let contributions = [33, 33, 33, 2];
+        let folded = [false, false, false, true];
+        let pots = build_pots(&contributions, &folded);
+        assert_eq!(pots, vec![pot(101, &[0, 1, 2])]);
+        let ranks = [1, 9, 1, 0];
+        assert_eq!(award_pots(&pots, &ranks, 0), vec![51, 0, 50, 0]);
+        // With the button on seat 0, seat 1 is first; seat 2 is then the
+        // first winner
+        assert_eq!(award_pots(&pots, &ranks, 1), vec![50, 0, 51, 0]);
+        // Wrapping around the table from seat 3
+        assert_eq!(award_pots(&pots, &ranks, 3), vec![51, 0, 50, 0]);
+    }
+
+    #[test]
+    fn several_odd_chips_go_one_each_in_seat_order() {
+        // 11 chips between three tied winners: 3 each, 2 left over
+        let pots = vec![pot(11, &[0, 2, 4])];
+        let ranks = [4, 0, 4, 0, 4];
+        assert_eq!(award_pots(&pots, &ranks, 2), vec![3, 0, 4, 0, 4]);
+        assert_eq!(award_pots(&pots, &ranks, 4), vec![4, 0, 3, 0, 4]);
+    }
+
+    #[test]
+    fn odd_chips_are_settled_pot_by_pot() {
+        // Main pot of 3 and side pot of 4, each chopped by seats 1 and 2
+        let contributions = [1, 3, 3];
+        let pots = build_pots(&contributions, &[false; 3]);
+        assert_eq!(pots, vec![pot(3, &[0, 1, 2]), pot(4, &[1, 2])]);
+        let won = award_pots(&pots, &[9, 1, 1], 2);
+        assert_eq!(won, vec![0, 3, 4]);
+    }
+
+    #[test]
+    fn five_way_all_in_conserves_chips() {
+        let contributions = [500, 20, 1000, 75, 300];
+        let pots = build_pots(&contributions, &[false; 5]);
+        assert_eq!(
+            pots,
+            vec![
+                pot(100, &[0, 1, 2, 3, 4]),
+                pot(220, &[0, 2, 3, 4]),
+                pot(675, &[0, 2, 4]),
+                pot(400, &[0, 2]),
+                pot(500, &[2]),
+            ]
+        );
+        let total: u32 = contributions.iter().sum();
+        for ranks in [
+            [1, 2, 3, 4, 5],
+            [5, 1, 4, 2, 3],
+            [3, 3, 3, 3, 3],
+            [2, 1, 2, 1, 2],
+        ] {
+            let won = showdown(&contributions, &ranks, 1);
+            assert_eq!(won.iter().sum::<u32>(), total, "ranks {:?}", ranks);
+        }
+        // Shortest stack best: each pot goes to the best hand still eligible
+        assert_eq!(
+            showdown(&contributions, &[5, 1, 4, 2, 3], 0),
+            vec![0, 100, 900, 220, 675]
+        );
+    }
+
+    #[test]
+    fn settle_credits_balances() {
+        let mut players: Vec<Player> = (0..3).map(|_| Player::new()).collect();
+        for (pl, put_in) in players.iter_mut().zip([100, 300, 300]) {
+            pl.wager(put_in);
+        }
+        players[2].fold();
+        let won = settle(&mut players, &[1, 2, 0], 0);
+        assert_eq!(won, vec![300, 400, 0]);
+        let balances: Vec<u32> = players.iter().map(|pl| pl.balance).collect();
+        assert_eq!(balances, vec![5200, 5100, 4700]);
+    }
+}
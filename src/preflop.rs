@@ -0,0 +1,556 @@
+use crate::batch::evaluate_batch_on_board;
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::isomorphism::canonicalize;
+use crate::probability::EquityResult;
+use crate::starting_hand::StartingHand;
+use itertools::Itertools;
+use std::io::{self, Read, Write};
+use std::sync::OnceLock;
+use std::thread;
+
+// Exact heads-up results of every pair of disjoint combos over all 1,712,304
+// boards they can see. Pairs of combos that only differ by relabelling suits
+// (AhKh against QhJh and AsKs against QsJs) share a suit pattern and the same
+// counts, so the table keeps one entry per pattern, keyed by the canonical
+// pair, plus the totals of each of the 169 × 169 starting hand matchups.
+// `PreflopTable::compute` builds the table and the crate ships it in
+// `data/preflop_equity.bin`, so preflop heads-up queries are a lookup.
+//
+// The computation walks boards rather than matchups: each 5-card board is
+// ranked against all 1081 pairs of the cards left, and every two disjoint
+// pairs make one showdown, counted under their pattern. Boards that only
+// differ by relabelling suits give the same counts for every pattern, so one
+// board of each such class is ranked and weighted by the size of the class.
+
+const HANDS: usize = 169;
+const COMBOS: usize = 1326;
+
+// File header; the counts follow as little-endian u32s: the 169 × 169 wins
+// and ties, the number of patterns, then the key, wins and ties of each.
+const MAGIC: &[u8; 4] = b"PFE2";
+
+/// Heads-up results of one starting hand or combo against another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Matchup {
+    pub wins: u64,
+    pub ties: u64,
+    pub losses: u64,
+}
+
+impl Matchup {
+    pub fn total(&self) -> u64 {
+        self.wins + self.ties + self.losses
+    }
+
+    /// The counts as fractions, with ties worth half the pot.
+    pub fn result(&self) -> EquityResult {
+        let total = self.total() as f64;
+        if total == 0.0 {
+            return EquityResult::default();
+        }
+        EquityResult {
+            win: self.wins as f64 / total,
+            tie: self.ties as f64 / total,
+            lose: self.losses as f64 / total,
+            equity: (self.wins as f64 + self.ties as f64 / 2.0) / total,
+            std_error: 0.0,
+        }
+    }
+}
+
+/// Exact heads-up counts for every suit pattern of two combos, and their
+/// totals for every ordered pair of the 169 starting hands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflopTable {
+    // Indexed by `hero.index() * 169 + villain.index()`
+    wins_: Vec<u32>,
+    ties_: Vec<u32>,
+    // Sorted keys of every pattern, with the counts of one pair of combos
+    pattern_keys_: Vec<u32>,
+    pattern_wins_: Vec<u32>,
+    pattern_ties_: Vec<u32>,
+}
+
+impl PreflopTable {
+    /// Enumerates every board on all available cores. This takes minutes;
+    /// use [`preflop_table`] for the precomputed result.
+    pub fn compute() -> PreflopTable {
+        let patterns = Patterns::new();
+        let boards = canonical_boards();
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let counts = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|worker| {
+                    let (boards, patterns) = (&boards, &patterns);
+                    scope.spawn(move || {
+                        let mut counts = BoardCounts::new(patterns);
+                        for &(board, weight) in boards.iter().skip(worker).step_by(threads) {
+                            counts.add_board(board, weight);
+                        }
+                        counts
+                    })
+                })
+                .collect();
+            let mut workers = workers.into_iter().map(|worker| worker.join().unwrap());
+            let mut counts = workers.next().expect("at least one worker");
+            for other in workers {
+                counts.merge(&other);
+            }
+            counts
+        });
+
+        // Each pattern's counts are summed over all of its pairs of combos
+        let narrow = |count: u64| u32::try_from(count).expect("matchup counts fit in 32 bits");
+        let mut wins = vec![0; HANDS * HANDS];
+        let mut ties = vec![0; HANDS * HANDS];
+        let mut table = PreflopTable {
+            wins_: Vec::new(),
+            ties_: Vec::new(),
+            pattern_keys_: patterns.keys.clone(),
+            pattern_wins_: Vec::with_capacity(patterns.keys.len()),
+            pattern_ties_: Vec::with_capacity(patterns.keys.len()),
+        };
+        for (pattern, &key) in patterns.keys.iter().enumerate() {
+            let [hero, villain] = key_hands(key);
+            wins[hero * HANDS + villain] += counts.wins[pattern];
+            ties[hero * HANDS + villain] += counts.ties[pattern];
+            let size = patterns.sizes[pattern];
+            debug_assert!(counts.wins[pattern] % size == 0 && counts.ties[pattern] % size == 0);
+            table
+                .pattern_wins_
+                .push(narrow(counts.wins[pattern] / size));
+            table
+                .pattern_ties_
+                .push(narrow(counts.ties[pattern] / size));
+        }
+        table.wins_ = wins.into_iter().map(narrow).collect();
+        table.ties_ = ties.into_iter().map(narrow).collect();
+        table
+    }
+
+    pub fn matchup(&self, hero: StartingHand, villain: StartingHand) -> Matchup {
+        let (hero, villain) = (hero.index(), villain.index());
+        Matchup {
+            wins: self.wins_[hero * HANDS + villain].into(),
+            ties: self.ties_[hero * HANDS + villain].into(),
+            losses: self.wins_[villain * HANDS + hero].into(),
+        }
+    }
+
+    /// Equity of `hero` against `villain`, averaged over every pair of
+    /// disjoint combos.
+    pub fn equity(&self, hero: StartingHand, villain: StartingHand) -> EquityResult {
+        self.matchup(hero, villain).result()
+    }
+
+    /// Equity of `hero` against any two other cards.
+    pub fn equity_vs_random(&self, hero: StartingHand) -> EquityResult {
+        let mut total = Matchup::default();
+        for villain in StartingHand::all() {
+            let matchup = self.matchup(hero, villain);
+            total.wins += matchup.wins;
+            total.ties += matchup.ties;
+            total.losses += matchup.losses;
+        }
+        total.result()
+    }
+
+    /// Results of particular hole cards against particular hole cards over
+    /// every board, or `None` if the four cards aren't all different.
+    pub fn combo_matchup(&self, hero: [Card; 2], villain: [Card; 2]) -> Option<Matchup> {
+        if hero[0] == hero[1]
+            || villain[0] == villain[1]
+            || hero.iter().any(|card| villain.contains(card))
+        {
+            return None;
+        }
+        let find = |key: u32| {
+            self.pattern_keys_
+                .binary_search(&key)
+                .expect("every pattern is in the table")
+        };
+        let (pattern, swapped) = (
+            find(pattern_key(hero, villain)),
+            find(pattern_key(villain, hero)),
+        );
+        Some(Matchup {
+            wins: self.pattern_wins_[pattern].into(),
+            ties: self.pattern_ties_[pattern].into(),
+            losses: self.pattern_wins_[swapped].into(),
+        })
+    }
+
+    /// Equity of particular hole cards against particular hole cards.
+    pub fn combo_equity(&self, hero: [Card; 2], villain: [Card; 2]) -> Option<EquityResult> {
+        self.combo_matchup(hero, villain)
+            .map(|matchup| matchup.result())
+    }
+
+    /// Writes the table in the format [`PreflopTable::read_from`] reads.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        for count in self.wins_.iter().chain(&self.ties_) {
+            writer.write_all(&count.to_le_bytes())?;
+        }
+        writer.write_all(&(self.pattern_keys_.len() as u32).to_le_bytes())?;
+        for count in self
+            .pattern_keys_
+            .iter()
+            .chain(&self.pattern_wins_)
+            .chain(&self.pattern_ties_)
+        {
+            writer.write_all(&count.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<PreflopTable> {
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a preflop equity table"));
+        }
+        let mut read_counts = |len: usize| -> io::Result<Vec<u32>> {
+            let mut bytes = vec![0; len * 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+                .collect())
+        };
+        let wins_ = read_counts(HANDS * HANDS)?;
+        let ties_ = read_counts(HANDS * HANDS)?;
+        let patterns = read_counts(1)?[0] as usize;
+        if patterns > COMBOS * COMBOS {
+            return Err(invalid("too many suit patterns"));
+        }
+        let pattern_keys_ = read_counts(patterns)?;
+        if !pattern_keys_.is_sorted_by(|a, b| a < b) {
+            return Err(invalid("suit patterns out of order"));
+        }
+        Ok(PreflopTable {
+            wins_,
+            ties_,
+            pattern_keys_,
+            pattern_wins_: read_counts(patterns)?,
+            pattern_ties_: read_counts(patterns)?,
+        })
+    }
+}
+
+/// The table shipped with the crate, loaded on first use.
+pub fn preflop_table() -> &'static PreflopTable {
+    static TABLE: OnceLock<PreflopTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut bytes: &[u8] = include_bytes!("../data/preflop_equity.bin");
+        PreflopTable::read_from(&mut bytes).expect("the shipped preflop table is well formed")
+    })
+}
+
+// The canonical hero and villain cards of a matchup, six bits per card id,
+// highest first.
+fn pattern_key(hero: [Card; 2], villain: [Card; 2]) -> u32 {
+    let spot = canonicalize(&hero, &villain);
+    spot.hole_cards
+        .iter()
+        .chain(&spot.board)
+        .fold(0, |key, card| key << 6 | card.int() as u32)
+}
+
+// Starting hand indices of the hero and villain of a pattern
+fn key_hands(key: u32) -> [usize; 2] {
+    let card = |shift: u32| Card::from_id((key >> shift & 0x3f) as i32).expect("ids below 52");
+    [
+        StartingHand::from_cards(card(18), card(12)).index(),
+        StartingHand::from_cards(card(6), card(0)).index(),
+    ]
+}
+
+// One board of each class of boards that are equal up to relabelling suits,
+// with the number of boards in its class.
+fn canonical_boards() -> Vec<([u8; 5], u64)> {
+    let mut boards = Vec::new();
+    for mut board in CardSet::full().iter().combinations(5) {
+        let spot = canonicalize(&[], &board);
+        board.reverse();
+        if spot.board == board {
+            let ids = std::array::from_fn(|i| board[i].int() as u8);
+            boards.push((ids, spot.class_size() as u64));
+        }
+    }
+    boards
+}
+
+// The pattern of every ordered pair of disjoint combos.
+struct Patterns {
+    keys: Vec<u32>,
+    // Combo index of two distinct card ids, `[a * 52 + b]`
+    combo_at: Vec<u16>,
+    // `[hero * 1326 + villain]`, by combo index
+    pattern_at: Vec<u32>,
+    // The pattern with hero and villain swapped
+    swapped: Vec<u32>,
+    // How many ordered pairs of combos have each pattern
+    sizes: Vec<u64>,
+}
+
+impl Patterns {
+    fn new() -> Patterns {
+        let mut combos = Vec::with_capacity(COMBOS);
+        let mut combo_at = vec![0; 52 * 52];
+        for (a, b) in (0..52).tuple_combinations() {
+            combo_at[a * 52 + b] = combos.len() as u16;
+            combo_at[b * 52 + a] = combos.len() as u16;
+            combos.push([a, b].map(|id| Card::from_id(id as i32).expect("ids below 52")));
+        }
+        let disjoint = |hero: usize, villain: usize| {
+            !combos[hero]
+                .iter()
+                .any(|card| combos[villain].contains(card))
+        };
+
+        let mut keys_at = vec![u32::MAX; COMBOS * COMBOS];
+        for (hero, villain) in (0..COMBOS).cartesian_product(0..COMBOS) {
+            if disjoint(hero, villain) {
+                keys_at[hero * COMBOS + villain] = pattern_key(combos[hero], combos[villain]);
+            }
+        }
+        let mut keys = keys_at.clone();
+        keys.sort_unstable();
+        keys.dedup();
+        keys.pop(); // u32::MAX
+
+        let mut pattern_at = vec![0; COMBOS * COMBOS];
+        let mut swapped = vec![0; keys.len()];
+        let mut sizes = vec![0; keys.len()];
+        for (hero, villain) in (0..COMBOS).cartesian_product(0..COMBOS) {
+            if disjoint(hero, villain) {
+                let find = |key| keys.binary_search(&key).unwrap() as u32;
+                let pattern = find(keys_at[hero * COMBOS + villain]);
+                pattern_at[hero * COMBOS + villain] = pattern;
+                swapped[pattern as usize] = find(keys_at[villain * COMBOS + hero]);
+                sizes[pattern as usize] += 1;
+            }
+        }
+        Patterns {
+            keys,
+            combo_at,
+            pattern_at,
+            swapped,
+            sizes,
+        }
+    }
+}
+
+// Weighted showdown counts of each pattern gathered board by board, with
+// scratch space for ranking the pairs of one board.
+struct BoardCounts<'a> {
+    patterns: &'a Patterns,
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    pairs: Vec<[u8; 2]>,
+    combos: Vec<usize>,
+    masks: Vec<u64>,
+    ranks: Vec<u16>,
+}
+
+impl<'a> BoardCounts<'a> {
+    fn new(patterns: &'a Patterns) -> BoardCounts<'a> {
+        BoardCounts {
+            patterns,
+            wins: vec![0; patterns.keys.len()],
+            ties: vec![0; patterns.keys.len()],
+            pairs: Vec::with_capacity(1081),
+            combos: Vec::with_capacity(1081),
+            masks: Vec::with_capacity(1081),
+            ranks: Vec::with_capacity(1081),
+        }
+    }
+
+    fn merge(&mut self, other: &BoardCounts) {
+        for (count, other) in self.wins.iter_mut().zip(&other.wins) {
+            *count += other;
+        }
+        for (count, other) in self.ties.iter_mut().zip(&other.ties) {
+            *count += other;
+        }
+    }
+
+    // Adds every showdown between two disjoint pairs on `board`, `weight`
+    // times.
+    fn add_board(&mut self, board: [u8; 5], weight: u64) {
+        let patterns = self.patterns;
+        self.pairs.clear();
+        self.combos.clear();
+        self.masks.clear();
+        for (a, b) in (0..52)
+            .filter(|id| !board.contains(id))
+            .tuple_combinations()
+        {
+            self.pairs.push([a, b]);
+            self.combos
+                .push(patterns.combo_at[a as usize * 52 + b as usize] as usize);
+            self.masks.push(1 << a | 1 << b);
+        }
+        self.ranks.resize(self.pairs.len(), 0);
+        evaluate_batch_on_board(board, &self.pairs, &mut self.ranks);
+
+        for hero in 0..self.pairs.len() {
+            let row = &patterns.pattern_at[self.combos[hero] * COMBOS..][..COMBOS];
+            let (hero_rank, hero_mask) = (self.ranks[hero], self.masks[hero]);
+            for villain in hero + 1..self.pairs.len() {
+                if hero_mask & self.masks[villain] != 0 {
+                    continue;
+                }
+                let pattern = row[self.combos[villain]] as usize;
+                let villain_rank = self.ranks[villain];
+                if hero_rank < villain_rank {
+                    self.wins[pattern] += weight;
+                } else if hero_rank > villain_rank {
+                    self.wins[patterns.swapped[pattern] as usize] += weight;
+                } else {
+                    self.ties[pattern] += weight;
+                    self.ties[patterns.swapped[pattern] as usize] += weight;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enumeration::enumerate_range_equity;
+    use crate::range::Range;
+
+    const BOARDS: u64 = 1_712_304;
+
+    fn hand(notation: &str) -> StartingHand {
+        notation.parse().unwrap()
+    }
+
+    #[test]
+    fn aces_against_kings() {
+        let matchup = preflop_table().matchup(hand("AA"), hand("KK"));
+        assert_eq!(matchup.total(), 36 * BOARDS);
+        let equity = matchup.result().equity;
+        assert!((equity - 0.8195).abs() < 0.0005, "{}", equity);
+        assert_eq!(
+            preflop_table().matchup(hand("KK"), hand("AA")),
+            Matchup {
+                wins: matchup.losses,
+                ties: matchup.ties,
+                losses: matchup.wins,
+            }
+        );
+    }
+
+    #[test]
+    fn a_matchup_agrees_with_direct_enumeration() {
+        // The 36 pairs of combos fall into three suit patterns: no suit in
+        // common (6 pairs), one (24) and both (6)
+        let mut wins = 0.0;
+        let mut ties = 0.0;
+        for (aces, kings, pairs) in [
+            ("AhAs", "KdKc", 6.0),
+            ("AhAs", "KhKc", 24.0),
+            ("AhAs", "KhKs", 6.0),
+        ] {
+            let ranges = [aces.parse::<Range>().unwrap(), kings.parse().unwrap()];
+            let result = enumerate_range_equity(&ranges, &[]).unwrap()[0];
+            wins += pairs * BOARDS as f64 * result.win;
+            ties += pairs * BOARDS as f64 * result.tie;
+        }
+        let matchup = preflop_table().matchup(hand("AA"), hand("KK"));
+        assert_eq!(wins.round() as u64, matchup.wins);
+        assert_eq!(ties.round() as u64, matchup.ties);
+    }
+
+    fn cards(notation: &str) -> [Card; 2] {
+        crate::notation::parse_cards(notation)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn enumerated(hero: &str, villain: &str) -> Matchup {
+        let ranges = [hero.parse::<Range>().unwrap(), villain.parse().unwrap()];
+        let [hero, villain] = enumerate_range_equity(&ranges, &[]).unwrap()[..] else {
+            panic!("two players");
+        };
+        let count = |fraction: f64| (fraction * BOARDS as f64).round() as u64;
+        Matchup {
+            wins: count(hero.win),
+            ties: count(hero.tie),
+            losses: count(villain.win),
+        }
+    }
+
+    #[test]
+    fn a_combo_matchup_agrees_with_direct_enumeration() {
+        let table = preflop_table();
+        let suited = table.combo_matchup(cards("AhKh"), cards("QhJh")).unwrap();
+        assert_eq!(suited, enumerated("AhKh", "QhJh"));
+        assert_eq!(suited.total(), BOARDS);
+        let offsuit = table.combo_matchup(cards("AsKs"), cards("QhJh")).unwrap();
+        assert_eq!(offsuit, enumerated("AsKs", "QhJh"));
+        assert_ne!(suited, offsuit);
+
+        // Relabelling suits keeps the pattern; swapping seats swaps the counts
+        assert_eq!(
+            table.combo_matchup(cards("KdAd"), cards("JdQd")),
+            Some(suited)
+        );
+        assert_eq!(
+            table.combo_matchup(cards("QhJh"), cards("AsKs")),
+            Some(Matchup {
+                wins: offsuit.losses,
+                ties: offsuit.ties,
+                losses: offsuit.wins,
+            })
+        );
+        assert_eq!(table.combo_matchup(cards("AhKh"), cards("KhQh")), None);
+        assert_eq!(table.combo_equity(cards("AhKh"), cards("AhKh")), None);
+    }
+
+    #[test]
+    fn combo_matchups_add_up_to_the_starting_hand_totals() {
+        let table = preflop_table();
+        for (hero, villain) in [("AA", "KK"), ("AKs", "QJo"), ("72o", "72o"), ("T9s", "ATs")] {
+            let mut total = Matchup::default();
+            for hero in hand(hero).combos() {
+                for villain in hand(villain).combos() {
+                    if let Some(matchup) = table.combo_matchup(hero, villain) {
+                        total.wins += matchup.wins;
+                        total.ties += matchup.ties;
+                        total.losses += matchup.losses;
+                    }
+                }
+            }
+            assert_eq!(total, table.matchup(hand(hero), hand(villain)));
+        }
+    }
+
+    #[test]
+    fn the_table_reads_back_what_it_writes() {
+        let mut bytes = Vec::new();
+        preflop_table().write_to(&mut bytes).unwrap();
+        let patterns = preflop_table().pattern_keys_.len();
+        assert_eq!(patterns, 93_769);
+        assert_eq!(
+            bytes.len(),
+            4 + 2 * HANDS * HANDS * 4 + 4 + 3 * patterns * 4
+        );
+        let table = PreflopTable::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(&table, preflop_table());
+
+        bytes[0] = b'X';
+        let error = PreflopTable::read_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        bytes[0] = MAGIC[0];
+        let error = PreflopTable::read_from(&mut &bytes[..100]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -1,90 +1,228 @@
 use crate::card::Card;
-use crate::evaluator7::evaluate_7cards;
+use crate::card_set::CardSet;
+use crate::enumeration::{
+    enumerate_hand_probabilities, enumerate_range_equity, enumerate_win_probability,
+    hand_enumeration_count, range_enumeration_count, win_enumeration_count,
+};
+use crate::evaluator::evaluate_hand;
+use crate::hand::Hand;
+use crate::multideck::{evaluate_7cards_multideck, seven_card_evaluator};
+use crate::preflop::preflop_table;
+use crate::range::Range;
 use crate::rank::{describe_rank_category, get_rank_category, RankCategory};
-use rand::seq::SliceRandom;
+use crate::simulation::{confidence_interval, run_shards, ShardResult, SimulationConfig};
+use crate::starting_hand::StartingHand;
+use rand::Rng;
 use std::collections::HashMap;
+use std::fmt;
 
-// Number of simulations to run for Monte Carlo calculations
-const NUM_SIMULATIONS: usize = 10000;
+// Up to this many boards (or board and opponent-holding showdowns), results
+// are enumerated exactly instead of sampled. Covers the turn and river, and the
+// flop heads-up.
+pub const EXACT_ENUMERATION_THRESHOLD: u64 = 2_000_000;
 
-// Calculate probabilities of getting each hand category with the current cards
+// Create a shoe with the specified number of packs, minus one copy of each
+// known card
+fn remaining_deck(hole_cards: &[Card], community_cards: &[Card], pack_count: usize) -> Vec<Card> {
+    let mut copies = [pack_count; 52];
+    for card in hole_cards.iter().chain(community_cards.iter()) {
+        copies[card.int()] = copies[card.int()].saturating_sub(1);
+    }
+    let mut deck = Vec::with_capacity(52 * pack_count);
+    for _ in 0..pack_count {
+        deck.extend(CardSet::full().iter().filter(|card| {
+            let left = &mut copies[card.int()];
+            if *left == 0 {
+                return false;
+            }
+            *left -= 1;
+            true
+        }));
+    }
+    deck
+}
+
+// Calculate probabilities of getting each hand category with the current cards.
+// Enumerates every runout when there are few enough, otherwise simulates.
 pub fn calculate_hand_probabilities(
     hole_cards: &[Card],
     community_cards: &[Card],
     num_players: usize,
     pack_count: usize,
 ) -> HashMap<RankCategory, f64> {
-    let mut rng = rand::thread_rng();
-    let mut results = HashMap::new();
+    calculate_hand_probabilities_with_config(
+        hole_cards,
+        community_cards,
+        num_players,
+        pack_count,
+        &SimulationConfig::default(),
+        &mut rand::thread_rng(),
+    )
+    .percentages
+}
 
-    // Create a deck with the specified number of packs
-    let mut deck = Vec::new();
-    for _ in 0..pack_count {
-        for rank in 0..13 {
-            for suit in 0..4 {
-                deck.push(Card::from_id(rank * 4 + suit));
-            }
-        }
+/// Probability in percent of finishing with each hand category, with the
+/// standard error of each estimate in percentage points (0 when enumerated
+/// exactly). Categories that never came up are left out.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HandProbabilities {
+    pub percentages: HashMap<RankCategory, f64>,
+    pub std_errors: HashMap<RankCategory, f64>,
+}
+
+impl HandProbabilities {
+    /// 95% confidence interval, in percent, for one category.
+    pub fn confidence_interval(&self, category: RankCategory) -> (f64, f64) {
+        confidence_interval(
+            *self.percentages.get(&category).unwrap_or(&0.0),
+            *self.std_errors.get(&category).unwrap_or(&0.0),
+            100.0,
+        )
     }
+}
 
-    // Remove known cards from the deck
-    let known_cards: Vec<Card> = hole_cards
-        .iter()
-        .chain(community_cards.iter())
-        .cloned()
-        .collect();
-    deck.retain(|card| {
-        !known_cards
-            .iter()
-            .any(|known_card| known_card.id_ == card.id_)
-    });
+// Same as `calculate_hand_probabilities` with an explicit simulation budget
+// and thread count. Simulated runouts are seeded from `rng`, so a seeded
+// generator makes the result reproducible whatever the thread count.
+pub fn calculate_hand_probabilities_with_config<R: Rng + ?Sized>(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    num_players: usize,
+    pack_count: usize,
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> HandProbabilities {
+    let deck = remaining_deck(hole_cards, community_cards, pack_count);
+    if hand_enumeration_count(community_cards.len(), deck.len()) <= EXACT_ENUMERATION_THRESHOLD {
+        let percentages =
+            enumerate_hand_probabilities(hole_cards, community_cards, &deck, pack_count);
+        let std_errors = percentages
+            .keys()
+            .map(|&category| (category, 0.0))
+            .collect();
+        return HandProbabilities {
+            percentages,
+            std_errors,
+        };
+    }
+    simulate_hand_probabilities(
+        hole_cards,
+        community_cards,
+        num_players,
+        pack_count,
+        config,
+        rng,
+    )
+}
+
+// Monte Carlo estimate of the hand category probabilities
+pub fn simulate_hand_probabilities<R: Rng + ?Sized>(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    _num_players: usize,
+    pack_count: usize,
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> HandProbabilities {
+    let evaluate = seven_card_evaluator(pack_count);
+    let deck = card_ids(&remaining_deck(hole_cards, community_cards, pack_count));
+    let missing = 5 - community_cards.len();
 
-    // Run simulations
-    for _ in 0..NUM_SIMULATIONS {
-        // Shuffle the deck
-        let mut shuffled_deck = deck.clone();
-        shuffled_deck.shuffle(&mut rng);
+    // Hole cards, then the board; the last `missing` slots are dealt
+    let mut known = [0i32; 7];
+    for (slot, card) in known
+        .iter_mut()
+        .zip(hole_cards.iter().chain(community_cards))
+    {
+        *slot = card.int32();
+    }
 
-        // Complete the community cards if needed
-        let mut sim_community_cards = community_cards.to_vec();
-        while sim_community_cards.len() < 5 {
-            sim_community_cards.push(shuffled_deck.pop().unwrap());
+    let counts = run_shards(config, rng.gen(), |rng, iterations| {
+        let mut counts = CategoryCounts::default();
+        let mut deck = deck.clone();
+        let mut cards = known;
+        for _ in 0..iterations {
+            draw(&mut deck, missing, rng);
+            cards[7 - missing..].copy_from_slice(&deck[..missing]);
+            let [a, b, c, d, e, f, g] = cards;
+            counts.0[get_rank_category(evaluate(a, b, c, d, e, f, g)) as usize] += 1;
         }
+        counts
+    });
 
-        // Create a 7-card hand
-        let mut hand = hole_cards.to_vec();
-        hand.extend(sim_community_cards.iter().cloned());
-
-        // Extract card IDs for evaluation
-        let card_ids: Vec<i32> = hand.iter().map(|card| card.id_).collect();
-
-        // Evaluate the hand
-        let rank = evaluate_7cards(
-            card_ids[0],
-            card_ids[1],
-            card_ids[2],
-            card_ids[3],
-            card_ids[4],
-            card_ids[5],
-            card_ids[6],
-        );
-        let category = get_rank_category(rank);
+    // Convert counts to probabilities
+    let mut result = HandProbabilities::default();
+    for category in RankCategory::ALL {
+        if let Some((p, std_error)) = counts.estimate(category) {
+            result.percentages.insert(category, p * 100.0);
+            result.std_errors.insert(category, std_error * 100.0);
+        }
+    }
+    result
+}
 
-        // Increment the counter for this category
-        *results.entry(category).or_insert(0.0) += 1.0;
+fn card_ids(cards: &[Card]) -> Vec<i32> {
+    cards.iter().map(|card| card.int32()).collect()
+}
+
+// Moves `count` uniformly chosen cards to the front of `deck`: the first steps
+// of a Fisher-Yates shuffle. Any order of the deck is as good a start as
+// another, so it is never reset between iterations.
+fn draw<T, R: Rng + ?Sized>(deck: &mut [T], count: usize, rng: &mut R) {
+    for i in 0..count {
+        let j = rng.gen_range(i..deck.len());
+        deck.swap(i, j);
     }
+}
 
-    // Convert counts to probabilities
-    for (_, count) in results.iter_mut() {
-        *count = *count / NUM_SIMULATIONS as f64 * 100.0;
+// Outcomes per hand category, indexed by discriminant
+#[derive(Debug, Default)]
+struct CategoryCounts([u64; 10]);
+
+impl CategoryCounts {
+    fn total(&self) -> u64 {
+        self.0.iter().sum()
     }
 
-    results
+    // Observed frequency of a category and its standard error, or `None`
+    // when it never came up
+    fn estimate(&self, category: RankCategory) -> Option<(f64, f64)> {
+        let count = self.0[category as usize];
+        if count == 0 {
+            return None;
+        }
+        let n = self.total() as f64;
+        let p = count as f64 / n;
+        Some((p, (p * (1.0 - p) / n).sqrt()))
+    }
+}
+
+impl ShardResult for CategoryCounts {
+    fn merge(&mut self, other: &Self) {
+        for (count, other_count) in self.0.iter_mut().zip(other.0) {
+            *count += other_count;
+        }
+    }
+
+    fn std_error(&self) -> f64 {
+        if self.total() == 0 {
+            return f64::INFINITY;
+        }
+        RankCategory::ALL
+            .iter()
+            .filter_map(|&category| self.estimate(category))
+            .map(|(_, std_error)| std_error)
+            .fold(0.0, f64::max)
+    }
 }
 
 // Helper function to get ordered probabilities
 pub fn get_ordered_probabilities(probs: &HashMap<RankCategory, f64>) -> Vec<(RankCategory, f64)> {
     let mut ordered = vec![
+        (
+            RankCategory::FiveOfAKind,
+            *probs.get(&RankCategory::FiveOfAKind).unwrap_or(&0.0),
+        ),
         (
             RankCategory::StraightFlush,
             *probs.get(&RankCategory::StraightFlush).unwrap_or(&0.0),
@@ -126,107 +264,256 @@ pub fn get_ordered_probabilities(probs: &HashMap<RankCategory, f64>) -> Vec<(Ran
     ordered
 }
 
-// Calculate the probability of winning with the current cards
-pub fn calculate_win_probability(
-    hole_cards: &[Card],
-    community_cards: &[Card],
-    num_players: usize,
-    pack_count: usize,
-) -> f64 {
-    let mut rng = rand::thread_rng();
-    let mut wins = 0.0;
+/// Outcome of a showdown from our seat, as fractions of all boards considered.
+/// `win + tie + lose == 1`. `equity` is our expected share of the pot, where a
+/// k-way tie is worth 1/k.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EquityResult {
+    pub win: f64,
+    pub tie: f64,
+    pub lose: f64,
+    pub equity: f64,
+    /// Standard error of `equity`; 0 when enumerated exactly.
+    pub std_error: f64,
+}
 
-    // Create a deck with the specified number of packs
-    let mut deck = Vec::new();
-    for _ in 0..pack_count {
-        for rank in 0..13 {
-            for suit in 0..4 {
-                deck.push(Card::from_id(rank * 4 + suit));
-            }
-        }
+impl EquityResult {
+    /// 95% confidence interval for `equity`.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        confidence_interval(self.equity, self.std_error, 1.0)
     }
+}
 
-    // Remove known cards from the deck
-    let known_cards: Vec<Card> = hole_cards
-        .iter()
-        .chain(community_cards.iter())
-        .cloned()
-        .collect();
-    deck.retain(|card| {
-        !known_cards
-            .iter()
-            .any(|known_card| known_card.id_ == card.id_)
-    });
+// Running (weighted) counts of showdown outcomes, shared by the Monte Carlo
+// and exact engines.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Tally {
+    pub(crate) wins: f64,
+    pub(crate) ties: f64,
+    pub(crate) losses: f64,
+    pub(crate) equity: f64,
+    // Sum of squared pot shares, for the sampling variance of `equity`
+    pub(crate) equity_squares: f64,
+}
 
-    // Run simulations
-    for _ in 0..NUM_SIMULATIONS {
-        // Shuffle the deck
-        let mut shuffled_deck = deck.clone();
-        shuffled_deck.shuffle(&mut rng);
+impl Tally {
+    // Records one showdown in which `beaten` says whether any opponent had a
+    // strictly better hand and `tied_with` how many opponents had an equal one.
+    pub(crate) fn record(&mut self, beaten: bool, tied_with: usize) {
+        self.record_weighted(beaten, tied_with, 1.0);
+    }
 
-        // Complete the community cards if needed
-        let mut sim_community_cards = community_cards.to_vec();
-        while sim_community_cards.len() < 5 {
-            sim_community_cards.push(shuffled_deck.pop().unwrap());
+    pub(crate) fn record_weighted(&mut self, beaten: bool, tied_with: usize, weight: f64) {
+        if beaten {
+            self.losses += weight;
+        } else if tied_with > 0 {
+            let share = 1.0 / (tied_with + 1) as f64;
+            self.ties += weight;
+            self.equity += weight * share;
+            self.equity_squares += weight * share * share;
+        } else {
+            self.wins += weight;
+            self.equity += weight;
+            self.equity_squares += weight;
         }
+    }
+
+    pub(crate) fn total(&self) -> f64 {
+        self.wins + self.ties + self.losses
+    }
 
-        // Create opponent hands
-        let mut opponent_hands = Vec::new();
-        for _ in 0..num_players - 1 {
-            let mut opponent_hand = Vec::new();
-            opponent_hand.push(shuffled_deck.pop().unwrap());
-            opponent_hand.push(shuffled_deck.pop().unwrap());
-            opponent_hands.push(opponent_hand);
+    // Fractions of an exact enumeration
+    pub(crate) fn result(&self) -> EquityResult {
+        let total = self.total();
+        if total == 0.0 {
+            return EquityResult::default();
+        }
+        EquityResult {
+            win: self.wins / total,
+            tie: self.ties / total,
+            lose: self.losses / total,
+            equity: self.equity / total,
+            std_error: 0.0,
         }
+    }
 
-        // Evaluate our hand
-        let mut our_hand = hole_cards.to_vec();
-        our_hand.extend(sim_community_cards.iter().cloned());
-
-        // Extract card IDs for our hand
-        let our_card_ids: Vec<i32> = our_hand.iter().map(|card| card.id_).collect();
-        let our_rank = evaluate_7cards(
-            our_card_ids[0],
-            our_card_ids[1],
-            our_card_ids[2],
-            our_card_ids[3],
-            our_card_ids[4],
-            our_card_ids[5],
-            our_card_ids[6],
-        );
+    // Fractions of a Monte Carlo run, with the standard error of the equity
+    pub(crate) fn sampled_result(&self) -> EquityResult {
+        EquityResult {
+            std_error: self.std_error(),
+            ..self.result()
+        }
+    }
+}
 
-        // Check if we win against all opponents
-        let mut won = true;
-        for opponent_hand in opponent_hands {
-            let mut full_opponent_hand = opponent_hand;
-            full_opponent_hand.extend(sim_community_cards.iter().cloned());
-
-            // Extract card IDs for opponent hand
-            let opponent_card_ids: Vec<i32> =
-                full_opponent_hand.iter().map(|card| card.id_).collect();
-            let opponent_rank = evaluate_7cards(
-                opponent_card_ids[0],
-                opponent_card_ids[1],
-                opponent_card_ids[2],
-                opponent_card_ids[3],
-                opponent_card_ids[4],
-                opponent_card_ids[5],
-                opponent_card_ids[6],
-            );
+impl ShardResult for Tally {
+    fn merge(&mut self, other: &Self) {
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.losses += other.losses;
+        self.equity += other.equity;
+        self.equity_squares += other.equity_squares;
+    }
 
-            if opponent_rank < our_rank {
-                won = false;
-                break;
-            }
+    fn std_error(&self) -> f64 {
+        let n = self.total();
+        if n < 2.0 {
+            return f64::INFINITY;
         }
+        let mean = self.equity / n;
+        let variance = (self.equity_squares / n - mean * mean).max(0.0);
+        (variance / (n - 1.0)).sqrt()
+    }
+}
 
-        if won {
-            wins += 1.0;
+// One tally per player of a multiway run
+impl ShardResult for Vec<Tally> {
+    fn merge(&mut self, other: &Self) {
+        if self.len() < other.len() {
+            self.resize(other.len(), Tally::default());
+        }
+        for (tally, other_tally) in self.iter_mut().zip(other) {
+            tally.merge(other_tally);
         }
     }
 
-    // Return win probability as a percentage
-    wins / NUM_SIMULATIONS as f64 * 100.0
+    fn std_error(&self) -> f64 {
+        self.iter().map(ShardResult::std_error).fold(0.0, f64::max)
+    }
+}
+
+// Records one multiway showdown: `ranks[i]` is player i's hand rank.
+pub(crate) fn record_showdown(ranks: &[i32], tallies: &mut [Tally], weight: f64) {
+    let best = *ranks.iter().min().expect("at least one player");
+    let at_best = ranks.iter().filter(|&&rank| rank == best).count();
+    for (tally, &rank) in tallies.iter_mut().zip(ranks) {
+        tally.record_weighted(rank != best, at_best - 1, weight);
+    }
+}
+
+// Calculate the win/tie/lose split and pot equity with the current cards. Looks
+// preflop heads-up spots up in the precomputed table, enumerates every runout
+// and opponent holding when there are few enough, otherwise simulates.
+pub fn calculate_win_probability(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    num_players: usize,
+    pack_count: usize,
+) -> EquityResult {
+    calculate_win_probability_with_config(
+        hole_cards,
+        community_cards,
+        num_players,
+        pack_count,
+        &SimulationConfig::default(),
+        &mut rand::thread_rng(),
+    )
+}
+
+// Same as `calculate_win_probability` with an explicit simulation budget and
+// thread count, seeding any simulated deals from `rng`.
+pub fn calculate_win_probability_with_config<R: Rng + ?Sized>(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    num_players: usize,
+    pack_count: usize,
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> EquityResult {
+    // Heads-up before the flop, the exact answer is precomputed
+    if community_cards.is_empty() && num_players == 2 && pack_count == 1 {
+        let hand = StartingHand::from_cards(hole_cards[0], hole_cards[1]);
+        return preflop_table().equity_vs_random(hand);
+    }
+
+    let deck = remaining_deck(hole_cards, community_cards, pack_count);
+    let showdowns = win_enumeration_count(
+        community_cards.len(),
+        deck.len(),
+        num_players.saturating_sub(1),
+    );
+    if showdowns <= EXACT_ENUMERATION_THRESHOLD {
+        return enumerate_win_probability(
+            hole_cards,
+            community_cards,
+            num_players,
+            &deck,
+            pack_count,
+        );
+    }
+    simulate_win_probability(
+        hole_cards,
+        community_cards,
+        num_players,
+        pack_count,
+        config,
+        rng,
+    )
+}
+
+// Monte Carlo estimate of the showdown outcome
+pub fn simulate_win_probability<R: Rng + ?Sized>(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    num_players: usize,
+    pack_count: usize,
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> EquityResult {
+    let deck = remaining_deck(hole_cards, community_cards, pack_count);
+    let missing = 5 - community_cards.len();
+    let opponents = num_players.saturating_sub(1);
+    let hole = [hole_cards[0], hole_cards[1]];
+    let community = Hand::from_cards(community_cards);
+    let mut known_ids = [0i32; 5];
+    for (slot, card) in known_ids.iter_mut().zip(community_cards) {
+        *slot = card.int32();
+    }
+
+    let tally = run_shards(config, rng.gen(), |rng, iterations| {
+        let mut tally = Tally::default();
+        let mut deck = deck.clone();
+        let mut board_ids = known_ids;
+        for _ in 0..iterations {
+            // The runout, then two cards per opponent
+            draw(&mut deck, missing + 2 * opponents, rng);
+
+            // Hash the board once and add each player's hole cards to it.
+            // Multi-pack shoes can repeat cards, which needs the slower path.
+            let mut board = community;
+            for (slot, &card) in board_ids[5 - missing..].iter_mut().zip(&deck[..missing]) {
+                board.add_card(card);
+                *slot = card.int32();
+            }
+            let rank_of = |a: Card, b: Card| {
+                if pack_count == 1 {
+                    return evaluate_hand(&board.with_hole_cards(a, b)).value();
+                }
+                let [c, d, e, f, g] = board_ids;
+                evaluate_7cards_multideck(a.int32(), b.int32(), c, d, e, f, g)
+            };
+            let our_rank = rank_of(hole[0], hole[1]);
+
+            // Compare against every opponent: any better hand beats us, equal
+            // hands split the pot
+            let mut beaten = false;
+            let mut tied_with = 0;
+            for opponent in deck[missing..missing + 2 * opponents].chunks_exact(2) {
+                let opponent_rank = rank_of(opponent[0], opponent[1]);
+                if opponent_rank < our_rank {
+                    beaten = true;
+                    break;
+                }
+                if opponent_rank == our_rank {
+                    tied_with += 1;
+                }
+            }
+
+            tally.record(beaten, tied_with);
+        }
+        tally
+    });
+    tally.sampled_result()
 }
 
 // Implement necessary traits for RankCategory
@@ -263,3 +550,445 @@ impl std::fmt::Display for RankCategory {
         }
     }
 }
+
+// Consecutive rejected deals after which the range simulation gives up, e.g.
+// when every combination of the ranges shares a card.
+const MAX_REJECTED_DEALS: usize = 10_000;
+
+/// Why range equity could not be computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeEquityError {
+    /// No deal gives every player a combo from their range without two of
+    /// them sharing a card.
+    NoLegalDeal,
+    /// The simulation drew so many clashing deals in a row that it gave up;
+    /// the ranges barely fit together, if at all.
+    TooManyRejectedDeals,
+}
+
+impl fmt::Display for RangeEquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeEquityError::NoLegalDeal => {
+                write!(
+                    f,
+                    "no deal gives every range a combo without sharing a card"
+                )
+            }
+            RangeEquityError::TooManyRejectedDeals => write!(
+                f,
+                "gave up after {} clashing deals in a row",
+                MAX_REJECTED_DEALS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RangeEquityError {}
+
+// Per-player tallies of a range simulation shard, and whether it gave up
+#[derive(Default)]
+struct RangeTallies {
+    tallies: Vec<Tally>,
+    gave_up: bool,
+}
+
+impl ShardResult for RangeTallies {
+    fn merge(&mut self, other: &Self) {
+        self.tallies.merge(&other.tallies);
+        self.gave_up |= other.gave_up;
+    }
+
+    fn std_error(&self) -> f64 {
+        // Nothing more to learn once a shard has given up
+        if self.gave_up {
+            return 0.0;
+        }
+        self.tallies.std_error()
+    }
+}
+
+// Calculate each player's equity when every player holds a hand from a
+// weighted range. Combos that clash with the board or with each other are
+// never dealt together. Enumerates exactly when there are few enough deals,
+// otherwise simulates. Ranges are dealt from a single pack.
+pub fn calculate_range_equity(
+    ranges: &[Range],
+    community_cards: &[Card],
+) -> Result<Vec<EquityResult>, RangeEquityError> {
+    calculate_range_equity_with_config(
+        ranges,
+        community_cards,
+        &SimulationConfig::default(),
+        &mut rand::thread_rng(),
+    )
+}
+
+// Same as `calculate_range_equity` with an explicit simulation budget and
+// thread count, seeding any simulated deals from `rng`.
+pub fn calculate_range_equity_with_config<R: Rng + ?Sized>(
+    ranges: &[Range],
+    community_cards: &[Card],
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> Result<Vec<EquityResult>, RangeEquityError> {
+    let board: CardSet = community_cards.iter().collect();
+    let ranges: Vec<Range> = ranges.iter().map(|range| range.without(board)).collect();
+    let deals = range_enumeration_count(&ranges, community_cards.len());
+    if deals <= EXACT_ENUMERATION_THRESHOLD {
+        return enumerate_range_equity(&ranges, community_cards);
+    }
+    simulate_range_equity(&ranges, community_cards, config, rng)
+}
+
+// Hero's equity with known hole cards against opponents holding ranges.
+pub fn calculate_equity_vs_ranges(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    opponent_ranges: &[Range],
+) -> Result<EquityResult, RangeEquityError> {
+    calculate_equity_vs_ranges_with_config(
+        hole_cards,
+        community_cards,
+        opponent_ranges,
+        &SimulationConfig::default(),
+        &mut rand::thread_rng(),
+    )
+}
+
+// Same as `calculate_equity_vs_ranges` with an explicit simulation budget
+// and thread count, seeding any simulated deals from `rng`.
+pub fn calculate_equity_vs_ranges_with_config<R: Rng + ?Sized>(
+    hole_cards: &[Card],
+    community_cards: &[Card],
+    opponent_ranges: &[Range],
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> Result<EquityResult, RangeEquityError> {
+    let mut ranges = vec![Range::from_hole_cards(hole_cards[0], hole_cards[1])];
+    ranges.extend_from_slice(opponent_ranges);
+    Ok(calculate_range_equity_with_config(&ranges, community_cards, config, rng)?[0])
+}
+
+// Monte Carlo estimate of range-vs-range equity
+pub fn simulate_range_equity<R: Rng + ?Sized>(
+    ranges: &[Range],
+    community_cards: &[Card],
+    config: &SimulationConfig,
+    rng: &mut R,
+) -> Result<Vec<EquityResult>, RangeEquityError> {
+    let board_cards: CardSet = community_cards.iter().collect();
+    let ranges: Vec<Range> = ranges
+        .iter()
+        .map(|range| range.without(board_cards))
+        .collect();
+    if ranges.iter().any(Range::is_empty) {
+        return Err(RangeEquityError::NoLegalDeal);
+    }
+
+    // Cumulative weights per range for weighted sampling
+    let cumulative: Vec<Vec<f64>> = ranges
+        .iter()
+        .map(|range| {
+            range
+                .combos()
+                .iter()
+                .scan(0.0, |sum, &(_, weight)| {
+                    *sum += weight;
+                    Some(*sum)
+                })
+                .collect()
+        })
+        .collect();
+
+    let community = Hand::from_cards(community_cards);
+
+    let shards = run_shards(config, rng.gen(), |rng, iterations| {
+        let mut tallies = vec![Tally::default(); ranges.len()];
+        let mut holdings: Vec<[Card; 2]> = Vec::with_capacity(ranges.len());
+        let mut ranks: Vec<i32> = vec![0; ranges.len()];
+        let mut gave_up = false;
+        'simulations: for _ in 0..iterations {
+            // Deal every player a combo from their range; start over on a clash
+            // so the deal follows the joint distribution after card removal
+            let mut rejected = 0;
+            let used = loop {
+                holdings.clear();
+                let mut used = board_cards;
+                for (range, weights) in ranges.iter().zip(&cumulative) {
+                    let total = *weights.last().expect("range is not empty");
+                    let pick = rng.gen_range(0.0..total);
+                    let index = weights
+                        .partition_point(|&w| w <= pick)
+                        .min(weights.len() - 1);
+                    let combo = range.combos()[index].0;
+                    if used.contains(combo[0]) || used.contains(combo[1]) {
+                        break;
+                    }
+                    used.insert(combo[0]);
+                    used.insert(combo[1]);
+                    holdings.push(combo);
+                }
+                if holdings.len() == ranges.len() {
+                    break used;
+                }
+                rejected += 1;
+                if rejected >= MAX_REJECTED_DEALS {
+                    gave_up = true;
+                    break 'simulations;
+                }
+            };
+
+            // Complete the community cards from what is left, redrawing any
+            // card already in play
+            let mut board = community;
+            let mut dealt = used.mask();
+            for _ in community_cards.len()..5 {
+                let id = loop {
+                    let id = rng.gen_range(0..52);
+                    if dealt & (1 << id) == 0 {
+                        dealt |= 1 << id;
+                        break id;
+                    }
+                };
+                board.add_card(Card::from_id(id).expect("card ids are below 52"));
+            }
+
+            for (rank, combo) in ranks.iter_mut().zip(&holdings) {
+                *rank = evaluate_hand(&board.with_hole_cards(combo[0], combo[1])).value();
+            }
+            record_showdown(&ranks, &mut tallies, 1.0);
+        }
+        RangeTallies { tallies, gave_up }
+    });
+    if shards.gave_up {
+        return Err(RangeEquityError::TooManyRejectedDeals);
+    }
+    Ok(shards.tallies.iter().map(Tally::sampled_result).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_adds_up(result: &EquityResult) {
+        assert_close(result.win + result.tie + result.lose, 1.0);
+    }
+
+    #[test]
+    fn a_k_way_tie_is_worth_one_kth_of_the_pot() {
+        let mut tallies = vec![Tally::default(); 3];
+        record_showdown(&[40, 40, 40], &mut tallies, 1.0);
+        for tally in &tallies {
+            let result = tally.result();
+            assert_eq!((result.win, result.tie, result.lose), (0.0, 1.0, 0.0));
+            assert_close(result.equity, 1.0 / 3.0);
+        }
+
+        // Two of three chop, the third loses
+        let mut tallies = vec![Tally::default(); 3];
+        record_showdown(&[40, 40, 90], &mut tallies, 1.0);
+        record_showdown(&[10, 40, 90], &mut tallies, 1.0);
+        let results: Vec<EquityResult> = tallies.iter().map(Tally::result).collect();
+        assert_close(results[0].equity, 0.75);
+        assert_close(results[1].equity, 0.25);
+        assert_close(results[2].equity, 0.0);
+        assert_eq!((results[0].win, results[0].tie), (0.5, 0.5));
+        assert_eq!((results[1].tie, results[1].lose), (0.5, 0.5));
+        assert_eq!(results[2].lose, 1.0);
+        assert_close(results.iter().map(|result| result.equity).sum(), 1.0);
+    }
+
+    #[test]
+    fn a_royal_flush_on_the_board_splits_every_pot() {
+        let hole = parse_cards("2c 3d").unwrap();
+        let board = parse_cards("Ah Kh Qh Jh Th").unwrap();
+        // Enumerated heads-up and three-way, simulated four-way
+        for players in 2..=4 {
+            let config = SimulationConfig::fixed(5_000).with_threads(1);
+            let mut rng = StdRng::seed_from_u64(8);
+            let result =
+                calculate_win_probability_with_config(&hole, &board, players, 1, &config, &mut rng);
+            assert_eq!(
+                (result.win, result.tie, result.lose),
+                (0.0, 1.0, 0.0),
+                "{} players",
+                players
+            );
+            assert_close(result.equity, 1.0 / players as f64);
+        }
+    }
+
+    #[test]
+    fn outcomes_add_up_to_one() {
+        let hole = parse_cards("Ah Kd").unwrap();
+        for board in [
+            "Qh Jc 7s",
+            "Qh Jc 7s 2d",
+            "Qh Jc 7s 2d Kc",
+            "Th Jd Qc 2s 3s",
+        ] {
+            let board = parse_cards(board).unwrap();
+            for players in 2..=3 {
+                let config = SimulationConfig::fixed(5_000).with_threads(1);
+                let mut rng = StdRng::seed_from_u64(8);
+                let result = calculate_win_probability_with_config(
+                    &hole, &board, players, 1, &config, &mut rng,
+                );
+                assert_adds_up(&result);
+                assert!(result.equity >= result.win && result.equity <= result.win + result.tie);
+            }
+        }
+    }
+
+    #[test]
+    fn the_river_is_counted_exactly() {
+        // Only the 16 four-five holdings make a wheel and beat top set
+        let hole = parse_cards("Ah Ad").unwrap();
+        let board = parse_cards("As Kc 7d 2h 3s").unwrap();
+        let result = calculate_win_probability(&hole, &board, 2, 1);
+        assert_eq!(result.std_error, 0.0);
+        assert_eq!(result.win, 974.0 / 990.0);
+        assert_eq!(result.lose, 16.0 / 990.0);
+        assert_eq!(result.tie, 0.0);
+        assert_eq!(result.confidence_interval(), (result.equity, result.equity));
+    }
+
+    #[test]
+    fn the_turn_is_counted_exactly() {
+        // 46 rivers: the ten of hearts for a royal, 8 more hearts, 3 more
+        // tens, 16 cards pairing something and 18 blanks
+        let hole = parse_cards("Ah Kh").unwrap();
+        let board = parse_cards("Qh Jh 2c 3d").unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let config = SimulationConfig::default();
+        let hands =
+            calculate_hand_probabilities_with_config(&hole, &board, 2, 1, &config, &mut rng);
+        for (category, rivers) in [
+            (RankCategory::StraightFlush, 1.0),
+            (RankCategory::Flush, 8.0),
+            (RankCategory::Straight, 3.0),
+            (RankCategory::OnePair, 16.0),
+            (RankCategory::HighCard, 18.0),
+        ] {
+            assert_close(hands.percentages[&category], rivers / 46.0 * 100.0);
+            assert_eq!(hands.std_errors[&category], 0.0);
+        }
+        assert_eq!(hands.percentages.len(), 5);
+
+        let result = calculate_win_probability_with_config(&hole, &board, 2, 1, &config, &mut rng);
+        assert_eq!(result.std_error, 0.0);
+        assert_adds_up(&result);
+    }
+
+    #[test]
+    fn exact_and_simulated_results_agree() {
+        let hole = parse_cards("Ah Kd").unwrap();
+        let board = parse_cards("Qh Jc 7s 2d 9c").unwrap();
+        let exact = calculate_win_probability(&hole, &board, 3, 1);
+        assert_eq!(exact.std_error, 0.0);
+        let config = SimulationConfig::fixed(50_000).with_threads(1);
+        let mut rng = StdRng::seed_from_u64(7);
+        let simulated = simulate_win_probability(&hole, &board, 3, 1, &config, &mut rng);
+        assert!(simulated.std_error > 0.0);
+        assert!(
+            (simulated.equity - exact.equity).abs() < 4.0 * simulated.std_error,
+            "{} vs {}",
+            simulated.equity,
+            exact.equity
+        );
+
+        // Too many showdowns to enumerate on a three-way flop
+        let flop = &board[..3];
+        let mut rng = StdRng::seed_from_u64(7);
+        let result = calculate_win_probability_with_config(&hole, flop, 3, 1, &config, &mut rng);
+        assert!(result.std_error > 0.0);
+    }
+
+    #[test]
+    fn seeded_runs_do_not_depend_on_the_thread_count() {
+        let hole = parse_cards("Ah Kd").unwrap();
+        // Preflop spots, too big to enumerate, over more than a round of
+        // shards so both the shard split and the stopping check come into play
+        for config in [
+            SimulationConfig::fixed(20_000),
+            SimulationConfig::fixed(40_000).with_target_std_error(0.004),
+        ] {
+            let win = |threads| {
+                let config = config.with_threads(threads);
+                let mut rng = StdRng::seed_from_u64(12);
+                calculate_win_probability_with_config(&hole, &[], 4, 1, &config, &mut rng)
+            };
+            let hands = |threads| {
+                let config = config.with_threads(threads);
+                let mut rng = StdRng::seed_from_u64(12);
+                calculate_hand_probabilities_with_config(&hole, &[], 3, 1, &config, &mut rng)
+            };
+            let (single_win, single_hands) = (win(1), hands(1));
+            for threads in [2, 3, 8] {
+                assert_eq!(win(threads), single_win, "{} threads", threads);
+                assert_eq!(hands(threads), single_hands, "{} threads", threads);
+            }
+        }
+    }
+
+    fn ranges(notations: &[&str]) -> Vec<Range> {
+        notations
+            .iter()
+            .map(|notation| notation.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn ranges_sharing_every_card_have_no_legal_deal() {
+        let ranges = ranges(&["AhAs", "AhKh"]);
+        assert_eq!(
+            calculate_range_equity(&ranges, &[]),
+            Err(RangeEquityError::NoLegalDeal)
+        );
+        let board = parse_cards("2c 7d 9s Js").unwrap();
+        assert_eq!(
+            calculate_range_equity(&ranges, &board),
+            Err(RangeEquityError::NoLegalDeal)
+        );
+    }
+
+    #[test]
+    fn a_range_emptied_by_the_board_has_no_legal_deal() {
+        let ranges = ranges(&["AhAs", "KK"]);
+        let board = parse_cards("Ac 7d 2c").unwrap();
+        let hero = parse_cards("Ah As").unwrap();
+        assert_eq!(
+            calculate_equity_vs_ranges(&hero, &board, &ranges[1..]),
+            Ok(calculate_range_equity(&ranges, &board).unwrap()[0])
+        );
+        let emptied = parse_cards("Ah 7d 2c").unwrap();
+        assert_eq!(
+            calculate_range_equity(&ranges, &emptied),
+            Err(RangeEquityError::NoLegalDeal)
+        );
+    }
+
+    #[test]
+    fn the_simulation_gives_up_on_ranges_that_never_fit() {
+        let ranges = ranges(&["AhAs", "AhKh"]);
+        let config = SimulationConfig::fixed(1_000).with_threads(1);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(
+            simulate_range_equity(&ranges, &[], &config, &mut rng),
+            Err(RangeEquityError::TooManyRejectedDeals)
+        );
+    }
+}
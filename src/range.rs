@@ -0,0 +1,425 @@
+use crate::card::{Card, CardRank};
+use crate::card_set::CardSet;
+use crate::notation::parse_cards;
+use crate::starting_hand::StartingHand;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The 169 starting hands from strongest to weakest by all-in equity against
+/// one random hand, as `preflop_table().equity_vs_random` gives it.
+/// Percentage ranges ("top 15%") take hands from the front.
+pub const PREFLOP_ORDER: [&str; 169] = [
+    "AA", "KK", "QQ", "JJ", "TT", "99", "88", "AKs", "77", "AQs", "AJs", "AKo", "ATs", "AQo",
+    "AJo", "KQs", "66", "A9s", "ATo", "KJs", "A8s", "KTs", "KQo", "A7s", "A9o", "KJo", "55", "QJs",
+    "K9s", "A5s", "A6s", "A8o", "KTo", "QTs", "A4s", "A7o", "K8s", "A3s", "QJo", "K9o", "A5o",
+    "A6o", "Q9s", "K7s", "JTs", "A2s", "QTo", "44", "A4o", "K6s", "K8o", "Q8s", "A3o", "K5s",
+    "J9s", "Q9o", "JTo", "K7o", "A2o", "K4s", "Q7s", "K6o", "K3s", "T9s", "J8s", "33", "Q6s",
+    "Q8o", "K5o", "J9o", "K2s", "Q5s", "T8s", "K4o", "J7s", "Q4s", "Q7o", "T9o", "J8o", "K3o",
+    "Q6o", "Q3s", "98s", "T7s", "J6s", "K2o", "22", "Q2s", "Q5o", "J5s", "T8o", "J7o", "Q4o",
+    "97s", "J4s", "T6s", "J3s", "Q3o", "98o", "87s", "T7o", "J6o", "96s", "J2s", "Q2o", "T5s",
+    "J5o", "T4s", "97o", "86s", "J4o", "T6o", "95s", "T3s", "76s", "J3o", "87o", "T2s", "85s",
+    "96o", "J2o", "T5o", "94s", "75s", "T4o", "93s", "86o", "65s", "84s", "95o", "T3o", "92s",
+    "76o", "74s", "T2o", "54s", "85o", "64s", "83s", "94o", "75o", "82s", "73s", "93o", "65o",
+    "53s", "63s", "84o", "92o", "43s", "74o", "72s", "54o", "64o", "52s", "62s", "83o", "42s",
+    "82o", "73o", "53o", "63o", "32s", "43o", "72o", "52o", "62o", "42o", "32o",
+];
+
+/// Why a range string could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeParseError {
+    /// A comma-separated part is not valid range notation.
+    InvalidToken(String),
+    /// The weight after `:` is not a number in `0..=1`.
+    InvalidWeight(String),
+    /// A percentage range is not a number in `0..=100`.
+    InvalidPercentage(String),
+}
+
+impl fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeParseError::InvalidToken(token) => write!(f, "invalid range part \"{}\"", token),
+            RangeParseError::InvalidWeight(token) => write!(f, "invalid weight in \"{}\"", token),
+            RangeParseError::InvalidPercentage(token) => {
+                write!(f, "invalid percentage in \"{}\"", token)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeParseError {}
+
+/// A weighted set of two-card holdings.
+///
+/// Parsed from standard notation, comma separated:
+/// - pairs: `QQ`, `QQ+`, `99-66`
+/// - suited / offsuit / both: `AKs`, `AKo`, `AK`, with `+` (`ATs+`) and dash
+///   ranges sharing the high card (`A5s-A2s`) or the gap between the cards
+///   (`T9s-54s`)
+/// - specific combos: `AhKh`
+/// - percentages of all hands, strongest first: `top 15%` or `15%`
+/// - everything: `random` or `any`
+///
+/// Any part may carry a weight in `0..=1` after a colon (`AKo:0.5`); a later
+/// part overrides the weight of combos listed earlier.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Range {
+    combos_: Vec<([Card; 2], f64)>,
+}
+
+impl Range {
+    pub fn parse(input: &str) -> Result<Range, RangeParseError> {
+        let mut range = Range::default();
+        let mut index: HashMap<[Card; 2], usize> = HashMap::new();
+        for token in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let (body, weight) = match token.split_once(':') {
+                Some((body, weight)) => {
+                    let weight: f64 = weight
+                        .trim()
+                        .parse()
+                        .map_err(|_| RangeParseError::InvalidWeight(token.to_string()))?;
+                    if !(0.0..=1.0).contains(&weight) {
+                        return Err(RangeParseError::InvalidWeight(token.to_string()));
+                    }
+                    (body.trim(), weight)
+                }
+                None => (token, 1.0),
+            };
+            for combo in parse_token(body)? {
+                let combo = normalize(combo);
+                match index.get(&combo) {
+                    Some(&i) => range.combos_[i].1 = weight,
+                    None => {
+                        index.insert(combo, range.combos_.len());
+                        range.combos_.push((combo, weight));
+                    }
+                }
+            }
+        }
+        range.combos_.retain(|&(_, weight)| weight > 0.0);
+        Ok(range)
+    }
+
+    /// A range holding exactly these two cards.
+    pub fn from_hole_cards(a: Card, b: Card) -> Range {
+        Range {
+            combos_: vec![(normalize([a, b]), 1.0)],
+        }
+    }
+
+    /// Every possible holding, each with weight 1.
+    pub fn random() -> Range {
+        Range::from_starting_hands(&StartingHand::all())
+    }
+
+    pub fn from_starting_hands(hands: &[StartingHand]) -> Range {
+        Range {
+            combos_: hands
+                .iter()
+                .flat_map(|hand| hand.combos())
+                .map(|combo| (normalize(combo), 1.0))
+                .collect(),
+        }
+    }
+
+    /// The combos and their weights, higher card first in each combo.
+    pub fn combos(&self) -> &[([Card; 2], f64)] {
+        &self.combos_
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos_.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos_.is_empty()
+    }
+
+    /// Sum of all combo weights.
+    pub fn total_weight(&self) -> f64 {
+        self.combos_.iter().map(|&(_, weight)| weight).sum()
+    }
+
+    pub fn weight_of(&self, a: Card, b: Card) -> f64 {
+        let combo = normalize([a, b]);
+        self.combos_
+            .iter()
+            .find(|&&(c, _)| c == combo)
+            .map_or(0.0, |&(_, weight)| weight)
+    }
+
+    /// Card removal: drops every combo that uses one of the `dead` cards.
+    pub fn without(&self, dead: CardSet) -> Range {
+        Range {
+            combos_: self
+                .combos_
+                .iter()
+                .filter(|(combo, _)| !dead.contains(combo[0]) && !dead.contains(combo[1]))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Range::parse(s)
+    }
+}
+
+fn normalize(combo: [Card; 2]) -> [Card; 2] {
+    if combo[0] >= combo[1] {
+        combo
+    } else {
+        [combo[1], combo[0]]
+    }
+}
+
+// A hand shape: high rank, low rank and whether it is suited (None for "both"
+// or for pairs).
+type Shape = (CardRank, CardRank, Option<bool>);
+
+fn parse_shape(s: &str) -> Option<Shape> {
+    let chars: Vec<char> = s.chars().collect();
+    let (a, b, suited) = match chars[..] {
+        [a, b] => (a, b, None),
+        [a, b, 's' | 'S'] => (a, b, Some(true)),
+        [a, b, 'o' | 'O'] => (a, b, Some(false)),
+        _ => return None,
+    };
+    let a = CardRank::from_char(a)?;
+    let b = CardRank::from_char(b)?;
+    let (high, low) = if a >= b { (a, b) } else { (b, a) };
+    if high == low && suited.is_some() {
+        return None;
+    }
+    Some((high, low, suited))
+}
+
+fn expand_shape((high, low, suited): Shape) -> Vec<[Card; 2]> {
+    let hands = if high == low {
+        vec![StartingHand::Pair(high)]
+    } else {
+        match suited {
+            Some(true) => vec![StartingHand::Suited(high, low)],
+            Some(false) => vec![StartingHand::Offsuit(high, low)],
+            None => vec![
+                StartingHand::Suited(high, low),
+                StartingHand::Offsuit(high, low),
+            ],
+        }
+    };
+    hands.iter().flat_map(|hand| hand.combos()).collect()
+}
+
+fn parse_token(token: &str) -> Result<Vec<[Card; 2]>, RangeParseError> {
+    let invalid = || RangeParseError::InvalidToken(token.to_string());
+    let lower = token.to_ascii_lowercase();
+
+    if lower == "random" || lower == "any" {
+        return Ok(Range::random()
+            .combos_
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect());
+    }
+
+    if let Some(percent) = lower.strip_suffix('%') {
+        let percent: f64 = percent
+            .trim_start_matches("top")
+            .trim()
+            .parse()
+            .map_err(|_| RangeParseError::InvalidPercentage(token.to_string()))?;
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(RangeParseError::InvalidPercentage(token.to_string()));
+        }
+        return Ok(top_percent(percent));
+    }
+
+    if let Some(base) = token.strip_suffix('+') {
+        let (high, low, suited) = parse_shape(base).ok_or_else(invalid)?;
+        let shapes: Vec<Shape> = if high == low {
+            CardRank::ALL[high.index()..]
+                .iter()
+                .map(|&rank| (rank, rank, None))
+                .collect()
+        } else {
+            CardRank::ALL[low.index()..high.index()]
+                .iter()
+                .map(|&kicker| (high, kicker, suited))
+                .collect()
+        };
+        return Ok(shapes.into_iter().flat_map(expand_shape).collect());
+    }
+
+    if let Some((from, to)) = token.split_once('-') {
+        let (high_a, low_a, suited_a) = parse_shape(from.trim()).ok_or_else(invalid)?;
+        let (high_b, low_b, suited_b) = parse_shape(to.trim()).ok_or_else(invalid)?;
+        if suited_a != suited_b {
+            return Err(invalid());
+        }
+        let shapes: Vec<Shape> = if high_a == low_a && high_b == low_b {
+            let (lo, hi) = (high_a.min(high_b), high_a.max(high_b));
+            CardRank::ALL[lo.index()..=hi.index()]
+                .iter()
+                .map(|&rank| (rank, rank, None))
+                .collect()
+        } else if high_a == high_b && high_a != low_a && high_b != low_b {
+            let (lo, hi) = (low_a.min(low_b), low_a.max(low_b));
+            CardRank::ALL[lo.index()..=hi.index()]
+                .iter()
+                .map(|&kicker| (high_a, kicker, suited_a))
+                .collect()
+        } else if high_a != low_a
+            && high_b != low_b
+            && high_a.index() - low_a.index() == high_b.index() - low_b.index()
+        {
+            // Connector runs with a fixed gap, e.g. "T9s-54s" or "J9o-64o"
+            let gap = high_a.index() - low_a.index();
+            let (lo, hi) = (high_a.min(high_b), high_a.max(high_b));
+            CardRank::ALL[lo.index()..=hi.index()]
+                .iter()
+                .map(|&high| (high, CardRank::ALL[high.index() - gap], suited_a))
+                .collect()
+        } else {
+            return Err(invalid());
+        };
+        return Ok(shapes.into_iter().flat_map(expand_shape).collect());
+    }
+
+    // Specific combo, e.g. "AhKh"
+    if token.len() == 4 {
+        return match parse_cards(token) {
+            Ok(cards) if cards.len() == 2 => Ok(vec![[cards[0], cards[1]]]),
+            _ => Err(invalid()),
+        };
+    }
+
+    parse_shape(token).map(expand_shape).ok_or_else(invalid)
+}
+
+// The strongest starting hands covering `percent` of all 1326 combos.
+fn top_percent(percent: f64) -> Vec<[Card; 2]> {
+    let target = percent / 100.0 * 1326.0;
+    let mut combos = Vec::new();
+    for name in PREFLOP_ORDER {
+        if combos.len() as f64 >= target {
+            break;
+        }
+        let hand: StartingHand = name.parse().expect("PREFLOP_ORDER holds valid hands");
+        combos.extend(hand.combos());
+    }
+    combos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preflop::preflop_table;
+
+    fn count(input: &str) -> usize {
+        Range::parse(input).unwrap().len()
+    }
+
+    #[test]
+    fn preflop_order_follows_equity_against_a_random_hand() {
+        let table = preflop_table();
+        let equities: Vec<f64> = PREFLOP_ORDER
+            .iter()
+            .map(|name| table.equity_vs_random(name.parse().unwrap()).equity)
+            .collect();
+        for (i, pair) in equities.windows(2).enumerate() {
+            assert!(
+                pair[0] >= pair[1],
+                "{} before {}",
+                PREFLOP_ORDER[i],
+                PREFLOP_ORDER[i + 1]
+            );
+        }
+    }
+
+    #[test]
+    fn pairs() {
+        assert_eq!(count("QQ"), 6);
+        assert_eq!(count("QQ+"), 18);
+        assert_eq!(count("99-66"), 24);
+        assert_eq!(count("22-77"), 36);
+    }
+
+    #[test]
+    fn suited_offsuit_and_both() {
+        assert_eq!(count("AKs"), 4);
+        assert_eq!(count("AKo"), 12);
+        assert_eq!(count("AK"), 16);
+        assert_eq!(count("76s"), 4);
+        assert_eq!(count("ATs+"), 16);
+        assert_eq!(count("KTo+"), 36);
+        assert_eq!(count("QQ+, AKs, A5s-A2s, 76s"), 18 + 4 + 16 + 4);
+    }
+
+    #[test]
+    fn dash_ranges() {
+        assert_eq!(count("A5s-A2s"), 16);
+        assert_eq!(count("KQo-K9o"), 48);
+        assert_eq!(count("T9s-54s"), 24);
+        assert_eq!(count("54s-T9s"), 24);
+        assert_eq!(count("J9o-64o"), 72);
+        assert!(Range::parse("T9s-53s").is_err());
+        assert!(Range::parse("A5s-A2o").is_err());
+        assert!(Range::parse("QQ-AKs").is_err());
+    }
+
+    #[test]
+    fn specific_combos() {
+        let range = Range::parse("AhKh, 7c7d").unwrap();
+        assert_eq!(range.len(), 2);
+        let card = |name: &str| Card::from_name(name).unwrap();
+        assert_eq!(range.weight_of(card("Kh"), card("Ah")), 1.0);
+        assert_eq!(range.weight_of(card("Ah"), card("Kd")), 0.0);
+    }
+
+    #[test]
+    fn percentages_take_the_strongest_hands() {
+        let range = Range::parse("top 15%").unwrap();
+        assert!(range.len() as f64 >= 0.15 * 1326.0);
+        // Overshooting by at most one offsuit hand
+        assert!((range.len() as f64) < 0.15 * 1326.0 + 12.0);
+        assert_eq!(count("15%"), range.len());
+        assert_eq!(count("top 0%"), 0);
+        assert_eq!(count("top 100%"), 1326);
+        let card = |name: &str| Card::from_name(name).unwrap();
+        assert_eq!(range.weight_of(card("As"), card("Ad")), 1.0);
+        assert_eq!(range.weight_of(card("7s"), card("2d")), 0.0);
+        assert!(Range::parse("top 101%").is_err());
+    }
+
+    #[test]
+    fn random_holds_every_combo() {
+        assert_eq!(count("random"), 1326);
+        assert_eq!(count("any"), 1326);
+    }
+
+    #[test]
+    fn weights() {
+        let range = Range::parse("AKo:0.5").unwrap();
+        assert_eq!(range.len(), 12);
+        assert_eq!(range.total_weight(), 6.0);
+        // A later part overrides earlier weights; weight 0 drops the combos
+        let range = Range::parse("AK, AKs:0.25").unwrap();
+        assert_eq!(range.len(), 16);
+        assert_eq!(range.total_weight(), 12.0 + 1.0);
+        assert_eq!(count("QQ+, KK:0"), 12);
+        assert!(Range::parse("AK:1.5").is_err());
+        assert!(Range::parse("AK:x").is_err());
+    }
+
+    #[test]
+    fn invalid_parts() {
+        assert!(Range::parse("AKx").is_err());
+        assert!(Range::parse("AAs").is_err());
+        assert!(Range::parse("AhAh").is_err());
+    }
+}
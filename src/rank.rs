@@ -1,6 +1,6 @@
 use crate::seven_four_six_two::RANK_DESCRIPTION;
 pub const RANK_CATEGORY_DESCRIPTION: [&str; 10] = [
-    "",
+    "Five of a Kind",
     "Straight Flush",
     "Four of a Kind",
     "Full House",
@@ -12,8 +12,11 @@ pub const RANK_CATEGORY_DESCRIPTION: [&str; 10] = [
     "High Card",
 ];
 
+// Discriminants index RANK_CATEGORY_DESCRIPTION.
+#[derive(Debug)]
 pub enum RankCategory {
-    // FIVE_OF_A_KIND = 0, // Reserved
+    // Only possible with more than one pack, see `multideck.rs`
+    FiveOfAKind = 0,
     StraightFlush,
     FourOfAKind,
     FullHouse,
@@ -25,6 +28,22 @@ pub enum RankCategory {
     HighCard,
 }
 
+impl RankCategory {
+    /// Every category from best to worst.
+    pub const ALL: [RankCategory; 10] = [
+        RankCategory::FiveOfAKind,
+        RankCategory::StraightFlush,
+        RankCategory::FourOfAKind,
+        RankCategory::FullHouse,
+        RankCategory::Flush,
+        RankCategory::Straight,
+        RankCategory::ThreeOfAKind,
+        RankCategory::TwoPair,
+        RankCategory::OnePair,
+        RankCategory::HighCard,
+    ];
+}
+
 pub const fn get_rank_category(rank: i32) -> RankCategory {
     if rank > 6185 {
         return RankCategory::HighCard;
@@ -50,28 +69,54 @@ pub const fn get_rank_category(rank: i32) -> RankCategory {
     if rank > 10 {
         return RankCategory::FourOfAKind;
     } //  156 four-kind
-    return RankCategory::StraightFlush; //   10 straight-flushes
+    if rank > 0 {
+        return RankCategory::StraightFlush;
+    } //   10 straight-flushes
+    RankCategory::FiveOfAKind //   13 five-kind (ranks -12 to 0)
 }
 
 pub const fn describe_rank_category(category: RankCategory) -> &'static str {
-    return RANK_CATEGORY_DESCRIPTION[category as usize];
+    RANK_CATEGORY_DESCRIPTION[category as usize]
+}
+
+// Five of a kind from aces (rank -12) down to deuces (rank 0).
+const FIVE_OF_A_KIND_DESCRIPTION: [[&str; 2]; 13] = [
+    ["A A A A A", "Five Aces"],
+    ["K K K K K", "Five Kings"],
+    ["Q Q Q Q Q", "Five Queens"],
+    ["J J J J J", "Five Jacks"],
+    ["T T T T T", "Five Tens"],
+    ["9 9 9 9 9", "Five Nines"],
+    ["8 8 8 8 8", "Five Eights"],
+    ["7 7 7 7 7", "Five Sevens"],
+    ["6 6 6 6 6", "Five Sixes"],
+    ["5 5 5 5 5", "Five Fives"],
+    ["4 4 4 4 4", "Five Fours"],
+    ["3 3 3 3 3", "Five Threes"],
+    ["2 2 2 2 2", "Five Deuces"],
+];
+
+const fn rank_description(rank: i32) -> [&'static str; 2] {
+    if rank <= 0 {
+        FIVE_OF_A_KIND_DESCRIPTION[(rank + 12) as usize]
+    } else {
+        RANK_DESCRIPTION[rank as usize]
+    }
 }
 
 pub const fn describe_rank(rank: i32) -> &'static str {
-    return RANK_DESCRIPTION[rank as usize][1];
+    rank_description(rank)[1]
 }
 
 pub const fn describe_sample_hand(rank: i32) -> &'static str {
-    return RANK_DESCRIPTION[rank as usize][0];
+    rank_description(rank)[0]
 }
 
 pub const fn is_flush(rank: i32) -> bool {
-    let return_val: bool = match get_rank_category(rank) {
-        RankCategory::StraightFlush => true,
-        RankCategory::Flush => true,
-        _ => false,
-    };
-    return_val
+    matches!(
+        get_rank_category(rank),
+        RankCategory::StraightFlush | RankCategory::Flush
+    )
 }
 
 pub struct Rank {
@@ -83,23 +128,23 @@ impl Rank {
         self.value_
     }
     pub const fn category(&self) -> RankCategory {
-        return get_rank_category(self.value_);
+        get_rank_category(self.value_)
     }
 
     pub const fn describe_category(&self) -> &'static str {
-        return describe_rank_category(self.category());
+        describe_rank_category(self.category())
     }
 
     pub const fn describe_rank(&self) -> &'static str {
-        return describe_rank(self.value_);
+        describe_rank(self.value_)
     }
 
     pub const fn describe_sample_hand(&self) -> &'static str {
-        return describe_sample_hand(self.value_);
+        describe_sample_hand(self.value_)
     }
 
     pub const fn is_flush(&self) -> bool {
-        return is_flush(self.value_);
+        is_flush(self.value_)
     }
 
     pub fn from_value(value: i32) -> Self {
@@ -109,7 +154,7 @@ impl Rank {
 
 impl PartialEq for Rank {
     fn eq(&self, other: &Self) -> bool {
-        return &self.value_ == &other.value_;
+        self.value_ == other.value_
     }
 }
 
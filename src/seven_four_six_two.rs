@@ -1,4 +1,4 @@
-pub const RANK_DESCRIPTION: [[&str; 2]; 7463] = [
+pub static RANK_DESCRIPTION: [[&str; 2]; 7463] = [
     ["", ""],
     ["A K Q J T", "Royal Flush"],
     ["K Q J T 9", "King-High Straight Flush"],
@@ -0,0 +1,152 @@
+use crate::card::{Card, CardRank, Suit};
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the 169 strategically distinct hold'em starting hands, e.g. "QQ",
+/// "AKs" or "72o". Non-pairs always store the higher rank first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StartingHand {
+    Pair(CardRank),
+    Suited(CardRank, CardRank),
+    Offsuit(CardRank, CardRank),
+}
+
+impl StartingHand {
+    /// Classifies two hole cards.
+    pub fn from_cards(a: Card, b: Card) -> StartingHand {
+        let (high, low) = if a.rank() >= b.rank() {
+            (a.rank(), b.rank())
+        } else {
+            (b.rank(), a.rank())
+        };
+        if high == low {
+            StartingHand::Pair(high)
+        } else if a.suit() == b.suit() {
+            StartingHand::Suited(high, low)
+        } else {
+            StartingHand::Offsuit(high, low)
+        }
+    }
+
+    /// All 169 starting hands: pairs from aces down, then suited and offsuit
+    /// hands from the highest.
+    pub fn all() -> Vec<StartingHand> {
+        let mut hands = Vec::with_capacity(169);
+        for &rank in CardRank::ALL.iter().rev() {
+            hands.push(StartingHand::Pair(rank));
+        }
+        for (i, &high) in CardRank::ALL.iter().enumerate().rev() {
+            for &low in CardRank::ALL[..i].iter().rev() {
+                hands.push(StartingHand::Suited(high, low));
+            }
+        }
+        for (i, &high) in CardRank::ALL.iter().enumerate().rev() {
+            for &low in CardRank::ALL[..i].iter().rev() {
+                hands.push(StartingHand::Offsuit(high, low));
+            }
+        }
+        hands
+    }
+
+    /// Position of this hand in [`StartingHand::all`], 0..169.
+    pub const fn index(&self) -> usize {
+        match *self {
+            StartingHand::Pair(rank) => 12 - rank.index(),
+            StartingHand::Suited(high, low) => 13 + non_pair(high, low),
+            StartingHand::Offsuit(high, low) => 91 + non_pair(high, low),
+        }
+    }
+
+    pub const fn high(&self) -> CardRank {
+        match *self {
+            StartingHand::Pair(rank) => rank,
+            StartingHand::Suited(high, _) | StartingHand::Offsuit(high, _) => high,
+        }
+    }
+
+    pub const fn low(&self) -> CardRank {
+        match *self {
+            StartingHand::Pair(rank) => rank,
+            StartingHand::Suited(_, low) | StartingHand::Offsuit(_, low) => low,
+        }
+    }
+
+    /// Number of card combinations: 6 for pairs, 4 suited, 12 offsuit.
+    pub const fn combo_count(&self) -> usize {
+        match self {
+            StartingHand::Pair(_) => 6,
+            StartingHand::Suited(..) => 4,
+            StartingHand::Offsuit(..) => 12,
+        }
+    }
+
+    /// Every two-card combination of this hand, higher card first.
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::with_capacity(self.combo_count());
+        match *self {
+            StartingHand::Pair(rank) => {
+                for (i, &a) in Suit::ALL.iter().enumerate() {
+                    for &b in &Suit::ALL[i + 1..] {
+                        combos.push([Card::new(rank, b), Card::new(rank, a)]);
+                    }
+                }
+            }
+            StartingHand::Suited(high, low) => {
+                for suit in Suit::ALL {
+                    combos.push([Card::new(high, suit), Card::new(low, suit)]);
+                }
+            }
+            StartingHand::Offsuit(high, low) => {
+                for a in Suit::ALL {
+                    for b in Suit::ALL {
+                        if a != b {
+                            combos.push([Card::new(high, a), Card::new(low, b)]);
+                        }
+                    }
+                }
+            }
+        }
+        combos
+    }
+}
+
+// Position among the 78 suited (or offsuit) hands: higher top cards first,
+// then higher kickers.
+const fn non_pair(high: CardRank, low: CardRank) -> usize {
+    let (high, low) = (high.index(), low.index());
+    78 - high * (high + 1) / 2 + high - 1 - low
+}
+
+impl fmt::Display for StartingHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartingHand::Pair(rank) => write!(f, "{}{}", rank, rank),
+            StartingHand::Suited(high, low) => write!(f, "{}{}s", high, low),
+            StartingHand::Offsuit(high, low) => write!(f, "{}{}o", high, low),
+        }
+    }
+}
+
+impl FromStr for StartingHand {
+    type Err = ();
+
+    /// Reads "QQ", "AKs" or "AKo". The ranks may come in either order.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let (a, b, suited) = match chars[..] {
+            [a, b] => (a, b, None),
+            [a, b, 's' | 'S'] => (a, b, Some(true)),
+            [a, b, 'o' | 'O'] => (a, b, Some(false)),
+            _ => return Err(()),
+        };
+        let a = CardRank::from_char(a).ok_or(())?;
+        let b = CardRank::from_char(b).ok_or(())?;
+        let (high, low) = if a >= b { (a, b) } else { (b, a) };
+        match (high == low, suited) {
+            (true, None) => Ok(StartingHand::Pair(high)),
+            (false, Some(true)) => Ok(StartingHand::Suited(high, low)),
+            (false, Some(false)) => Ok(StartingHand::Offsuit(high, low)),
+            _ => Err(()),
+        }
+    }
+}
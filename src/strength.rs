@@ -0,0 +1,225 @@
+use crate::card::Card;
+use crate::evaluator::evaluate;
+use crate::rank::{get_rank_category, RankCategory};
+
+// How a hand stands on its board against every two cards an opponent could
+// hold. A raw rank compares against all 7,462 hand values in the abstract;
+// here only the holdings the board leaves possible count, so top pair on a
+// four-flush board can be weak while the same rank elsewhere is strong.
+
+/// Our hand against every holding an opponent could have on the board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelativeStrength {
+    /// Our rank, lower is better.
+    pub rank: i32,
+    /// Holdings our hand beats.
+    pub beats: usize,
+    /// Holdings making the same hand value.
+    pub ties: usize,
+    /// Holdings beating our hand.
+    pub loses: usize,
+    /// 1 when we hold the nuts, 2 when one hand value beats ours, and so on.
+    pub position: usize,
+    /// Rank of the best hand anyone can hold, ours included.
+    pub nuts_rank: i32,
+    /// One holding making the nuts, higher card first.
+    pub nuts: [Card; 2],
+}
+
+impl RelativeStrength {
+    pub fn total(&self) -> usize {
+        self.beats + self.ties + self.loses
+    }
+
+    pub fn is_nuts(&self) -> bool {
+        self.position == 1
+    }
+
+    pub fn nuts_category(&self) -> RankCategory {
+        get_rank_category(self.nuts_rank)
+    }
+
+    /// Share of holdings we beat, ties counting half, in percent.
+    pub fn percentile(&self) -> f64 {
+        if self.total() == 0 {
+            return 100.0;
+        }
+        (self.beats as f64 + self.ties as f64 / 2.0) / self.total() as f64 * 100.0
+    }
+
+    /// "the nuts", "2nd nuts", "3rd nuts" and so on.
+    pub fn position_name(&self) -> String {
+        if self.is_nuts() {
+            return "the nuts".to_owned();
+        }
+        let suffix = match (self.position % 10, self.position % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("{}{} nuts", self.position, suffix)
+    }
+}
+
+/// Ranks `hole_cards` on a flop, turn or river `board` against every two
+/// cards left in a shoe of `pack_count` packs.
+///
+/// # Panics
+///
+/// Panics unless there are 2 hole cards and 3 to 5 board cards.
+pub fn relative_strength(
+    hole_cards: &[Card],
+    board: &[Card],
+    pack_count: usize,
+) -> RelativeStrength {
+    assert_eq!(hole_cards.len(), 2, "need 2 hole cards");
+    assert!(
+        (3..=5).contains(&board.len()),
+        "a board has 3 to 5 cards, got {}",
+        board.len()
+    );
+    let mut cards = hole_cards.to_vec();
+    cards.extend_from_slice(board);
+    let rank = evaluate(&cards);
+
+    let mut strength = RelativeStrength {
+        rank,
+        beats: 0,
+        ties: 0,
+        loses: 0,
+        position: 1,
+        nuts_rank: rank,
+        nuts: [
+            hole_cards[0].max(hole_cards[1]),
+            hole_cards[0].min(hole_cards[1]),
+        ],
+    };
+    let mut better_values = Vec::new();
+    for (holding, other) in ranked_holdings(hole_cards, board, pack_count) {
+        if other > rank {
+            strength.beats += 1;
+        } else if other == rank {
+            strength.ties += 1;
+        } else {
+            strength.loses += 1;
+            better_values.push(other);
+        }
+        if other < strength.nuts_rank {
+            strength.nuts_rank = other;
+            strength.nuts = holding;
+        }
+    }
+    better_values.sort_unstable();
+    better_values.dedup();
+    strength.position = better_values.len() + 1;
+    strength
+}
+
+// Every holding an opponent could have, higher card first, with its rank on
+// `board`. Identical cards from a multi-pack shoe pair up while copies last.
+pub(crate) fn ranked_holdings(
+    hole_cards: &[Card],
+    board: &[Card],
+    pack_count: usize,
+) -> Vec<([Card; 2], i32)> {
+    let mut copies = [pack_count; 52];
+    for card in hole_cards.iter().chain(board) {
+        copies[card.int()] = copies[card.int()].saturating_sub(1);
+    }
+    let mut holdings = Vec::new();
+    for a in 0..52 {
+        for b in a..52 {
+            let available = if a == b {
+                copies[a] >= 2
+            } else {
+                copies[a] >= 1 && copies[b] >= 1
+            };
+            if !available {
+                continue;
+            }
+            let holding = [b, a].map(|id| Card::from_id(id as i32).expect("ids below 52"));
+            let mut cards = holding.to_vec();
+            cards.extend_from_slice(board);
+            holdings.push((holding, evaluate(&cards)));
+        }
+    }
+    holdings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    fn strength(hole: &str, board: &str, pack_count: usize) -> RelativeStrength {
+        relative_strength(
+            &parse_cards(hole).unwrap(),
+            &parse_cards(board).unwrap(),
+            pack_count,
+        )
+    }
+
+    #[test]
+    fn the_nuts_beat_everything() {
+        let royal = strength("Ah Kh", "Qh Jh Th 2c 3d", 1);
+        assert!(royal.is_nuts());
+        assert_eq!(royal.position, 1);
+        assert_eq!(royal.nuts_rank, royal.rank);
+        assert_eq!(royal.nuts_rank, 1);
+        assert_eq!(royal.nuts.to_vec(), parse_cards("Ah Kh").unwrap());
+        assert_eq!((royal.beats, royal.ties, royal.loses), (990, 0, 0));
+        assert_eq!(royal.percentile(), 100.0);
+        assert_eq!(royal.position_name(), "the nuts");
+    }
+
+    #[test]
+    fn nuts_that_can_be_split() {
+        // Any other ace-king makes the same broadway straight
+        let broadway = strength("Ah Kd", "Qc Jd Tc 2h 3s", 1);
+        assert!(broadway.is_nuts());
+        assert_eq!(broadway.nuts_category(), RankCategory::Straight);
+        assert_eq!((broadway.beats, broadway.ties, broadway.loses), (981, 9, 0));
+        assert!((broadway.percentile() - 985.5 / 990.0 * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn second_nuts_lose_to_one_hand_value() {
+        let boat = strength("Kd Kh", "Kc 9d 5h 2s 2c", 1);
+        assert_eq!(boat.position, 2);
+        assert_eq!(boat.position_name(), "2nd nuts");
+        assert_eq!(boat.nuts_category(), RankCategory::FourOfAKind);
+        assert_eq!(boat.nuts.to_vec(), parse_cards("2h 2d").unwrap());
+        assert_eq!((boat.beats, boat.ties, boat.loses), (989, 0, 1));
+    }
+
+    #[test]
+    fn a_second_pack_adds_identical_holdings() {
+        let royal = strength("Ah Kh", "Qh Jh Th", 2);
+        // Every pair of distinct cards, plus both copies of the 47 unseen
+        // cards that have two
+        assert_eq!(royal.total(), 1326 + 47);
+        assert_eq!(royal.ties, 1);
+        assert!(royal.is_nuts());
+    }
+
+    #[test]
+    fn position_names() {
+        let mut strength = strength("Kd Kh", "Kc 9d 5h 2s 2c", 1);
+        for (position, name) in [
+            (3, "3rd nuts"),
+            (4, "4th nuts"),
+            (11, "11th nuts"),
+            (12, "12th nuts"),
+            (13, "13th nuts"),
+            (21, "21st nuts"),
+            (22, "22nd nuts"),
+            (103, "103rd nuts"),
+            (111, "111th nuts"),
+        ] {
+            strength.position = position;
+            assert_eq!(strength.position_name(), name);
+        }
+    }
+}
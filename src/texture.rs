@@ -0,0 +1,344 @@
+use crate::card::{Card, CardRank, Suit};
+use crate::card_set::CardSet;
+use crate::draws::find_draws;
+use crate::evaluator::evaluate;
+use crate::rank::{get_rank_category, RankCategory};
+use crate::strength::ranked_holdings;
+use std::fmt;
+
+// Board texture: what the community cards alone say about the hands players
+// can hold. Wetness is measured rather than scored by rule of thumb: it is the
+// share of all holdings that have a straight or flush, or a draw to one.
+
+/// Repeated ranks on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pairing {
+    Unpaired,
+    Paired,
+    TwoPair,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+/// How the board's suits are spread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuitPattern {
+    /// No two cards share a suit.
+    Rainbow,
+    /// Some cards share a suit, but not all.
+    TwoTone,
+    /// Every card is of one suit.
+    Monotone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardTexture {
+    pub pairing: Pairing,
+    pub suits: SuitPattern,
+    /// Most board ranks within one five-rank straight, 1 to 5.
+    pub connectedness: usize,
+    pub high_card: CardRank,
+    /// Share of the holdings with a straight or flush or a draw to one, 0 to 1.
+    pub wetness: f64,
+    pub straight_possible: bool,
+    pub flush_possible: bool,
+    pub full_house_possible: bool,
+}
+
+/// Classifies a flop, turn or river.
+///
+/// # Panics
+///
+/// Panics unless `board` holds 3 to 5 cards.
+pub fn analyze_board(board: &[Card]) -> BoardTexture {
+    assert!(
+        (3..=5).contains(&board.len()),
+        "a board has 3 to 5 cards, got {}",
+        board.len()
+    );
+
+    let mut rank_counts = [0usize; 13];
+    for card in board {
+        rank_counts[card.rank().index()] += 1;
+    }
+    let mut counts = rank_counts;
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let pairing = match counts[..2] {
+        [4 | 5, _] => Pairing::Quads,
+        [3, 2] => Pairing::FullHouse,
+        [3, _] => Pairing::Trips,
+        [2, 2] => Pairing::TwoPair,
+        [2, _] => Pairing::Paired,
+        _ => Pairing::Unpaired,
+    };
+
+    // Identical cards from a multi-pack shoe count once towards a flush
+    let distinct: CardSet = board.iter().collect();
+    let suit_counts = Suit::ALL.map(|suit| distinct.iter().filter(|c| c.suit() == suit).count());
+    let most_suited = *suit_counts.iter().max().unwrap();
+    let suits = if most_suited == distinct.len() {
+        SuitPattern::Monotone
+    } else if most_suited == 1 {
+        SuitPattern::Rainbow
+    } else {
+        SuitPattern::TwoTone
+    };
+
+    // Straight windows from A-5 to T-A
+    let has_rank = |index: usize| rank_counts[index] > 0;
+    let connectedness = (0..10)
+        .map(|low| {
+            (low..low + 5)
+                .filter(|&i| has_rank(if i == 0 { 12 } else { i - 1 }))
+                .count()
+        })
+        .max()
+        .unwrap_or(0);
+
+    let high_card = board
+        .iter()
+        .map(|card| card.rank())
+        .max()
+        .expect("board is not empty");
+
+    BoardTexture {
+        pairing,
+        suits,
+        connectedness,
+        high_card,
+        wetness: wetness(board),
+        straight_possible: connectedness >= 3,
+        flush_possible: most_suited >= 3,
+        full_house_possible: pairing != Pairing::Unpaired,
+    }
+}
+
+// Share of two-card holdings from the rest of the pack with a straight or
+// flush, or a draw to one.
+fn wetness(board: &[Card]) -> f64 {
+    let left: Vec<Card> = board
+        .iter()
+        .collect::<CardSet>()
+        .complement()
+        .iter()
+        .collect();
+    let (mut wet, mut total) = (0usize, 0usize);
+    for (i, &a) in left.iter().enumerate() {
+        for &b in &left[i + 1..] {
+            total += 1;
+            let mut cards = vec![a, b];
+            cards.extend_from_slice(board);
+            let made = matches!(
+                get_rank_category(evaluate(&cards)),
+                RankCategory::StraightFlush | RankCategory::Flush | RankCategory::Straight
+            );
+            let drawing = find_draws(&[a, b], board)
+                .draws
+                .iter()
+                .any(|draw| draw.kind.is_flush_draw() || draw.kind.is_straight_draw());
+            if made || drawing {
+                wet += 1;
+            }
+        }
+    }
+    wet as f64 / total as f64
+}
+
+impl fmt::Display for BoardTexture {
+    /// E.g. "paired, two-tone, 3 connected, ace high, 25% wet; straight and
+    /// full house possible".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairing = match self.pairing {
+            Pairing::Unpaired => "unpaired",
+            Pairing::Paired => "paired",
+            Pairing::TwoPair => "two pair",
+            Pairing::Trips => "trips",
+            Pairing::FullHouse => "full house",
+            Pairing::Quads => "quads",
+        };
+        let suits = match self.suits {
+            SuitPattern::Rainbow => "rainbow",
+            SuitPattern::TwoTone => "two-tone",
+            SuitPattern::Monotone => "monotone",
+        };
+        write!(
+            f,
+            "{}, {}, {} connected, {} high, {:.0}% wet",
+            pairing,
+            suits,
+            self.connectedness,
+            self.high_card.clean_name().to_lowercase(),
+            self.wetness * 100.0
+        )?;
+        let possible: Vec<&str> = [
+            (self.straight_possible, "straight"),
+            (self.flush_possible, "flush"),
+            (self.full_house_possible, "full house"),
+        ]
+        .into_iter()
+        .filter_map(|(possible, name)| possible.then_some(name))
+        .collect();
+        match possible[..] {
+            [] => Ok(()),
+            [one] => write!(f, "; {} possible", one),
+            [ref rest @ .., last] => write!(f, "; {} and {} possible", rest.join(", "), last),
+        }
+    }
+}
+
+/// Made hands of one category that beat a player's hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threat {
+    pub category: RankCategory,
+    /// Holdings making this category and beating us.
+    pub combos: usize,
+    /// One such holding, higher card first.
+    pub example: [Card; 2],
+}
+
+/// Every category of made hand an opponent could hold on `board` that beats
+/// `hole_cards`, best category first. Holdings are dealt from what is left of
+/// a shoe of `pack_count` packs after our cards and the board.
+pub fn hands_that_beat(hole_cards: &[Card], board: &[Card], pack_count: usize) -> Vec<Threat> {
+    let mut known = hole_cards.to_vec();
+    known.extend_from_slice(board);
+    let our_rank = evaluate(&known);
+
+    let mut threats: Vec<Threat> = Vec::new();
+    for (holding, rank) in ranked_holdings(hole_cards, board, pack_count) {
+        if rank >= our_rank {
+            continue;
+        }
+        let category = get_rank_category(rank);
+        match threats
+            .iter_mut()
+            .find(|threat| threat.category == category)
+        {
+            Some(threat) => threat.combos += 1,
+            None => threats.push(Threat {
+                category,
+                combos: 1,
+                example: holding,
+            }),
+        }
+    }
+    threats.sort_by_key(|threat| threat.category as usize);
+    threats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+    use itertools::Itertools;
+
+    fn texture(board: &str) -> BoardTexture {
+        analyze_board(&parse_cards(board).unwrap())
+    }
+
+    #[test]
+    fn a_dry_rainbow_flop() {
+        let dry = texture("Kc 7d 2h");
+        assert_eq!(dry.pairing, Pairing::Unpaired);
+        assert_eq!(dry.suits, SuitPattern::Rainbow);
+        assert_eq!(dry.connectedness, 1);
+        assert_eq!(dry.high_card, CardRank::King);
+        // Two hole cards can't reach four to a straight or flush
+        assert_eq!(dry.wetness, 0.0);
+        assert!(!dry.straight_possible && !dry.flush_possible && !dry.full_house_possible);
+        assert_eq!(
+            dry.to_string(),
+            "unpaired, rainbow, 1 connected, king high, 0% wet"
+        );
+    }
+
+    #[test]
+    fn a_connected_two_tone_flop_is_wet() {
+        let wet = texture("9h 8h 7c");
+        assert_eq!(wet.suits, SuitPattern::TwoTone);
+        assert_eq!(wet.connectedness, 3);
+        assert!(wet.straight_possible);
+        assert!(!wet.flush_possible);
+        assert!(wet.wetness > texture("Kh 8h 2c").wetness);
+        assert!(wet.wetness > 0.3, "{}", wet.wetness);
+    }
+
+    #[test]
+    fn paired_suited_boards_allow_flushes_and_full_houses() {
+        let board = texture("Kh 9h Ks 4h");
+        assert_eq!(board.pairing, Pairing::Paired);
+        assert_eq!(board.suits, SuitPattern::TwoTone);
+        assert!(board.flush_possible && board.full_house_possible);
+        assert!(board
+            .to_string()
+            .ends_with("; flush and full house possible"));
+
+        // A monotone paired board needs a second pack; the repeated card
+        // counts once towards the flush
+        let cards = |names: &[&str]| -> Vec<Card> {
+            names
+                .iter()
+                .map(|name| Card::from_name(name).unwrap())
+                .collect()
+        };
+        let board = analyze_board(&cards(&["Kh", "Kh", "9h", "4h"]));
+        assert_eq!(board.pairing, Pairing::Paired);
+        assert_eq!(board.suits, SuitPattern::Monotone);
+        assert!(board.flush_possible && board.full_house_possible);
+        assert!(!analyze_board(&cards(&["Kh", "Kh", "9h"])).flush_possible);
+    }
+
+    #[test]
+    fn pairings() {
+        assert_eq!(texture("7c 7d 2h 2s").pairing, Pairing::TwoPair);
+        assert_eq!(texture("7c 7d 7h 2s").pairing, Pairing::Trips);
+        assert_eq!(texture("7c 7d 7h 2s 2c").pairing, Pairing::FullHouse);
+        assert_eq!(texture("7c 7d 7h 7s 2c").pairing, Pairing::Quads);
+        // The ace plays low too
+        assert_eq!(texture("Ac 2d 3h 9s").connectedness, 3);
+        let straight_board = texture("Th Jd Qc Ks As");
+        assert_eq!(straight_board.connectedness, 5);
+        assert_eq!(straight_board.wetness, 1.0);
+    }
+
+    #[test]
+    fn hands_that_beat_top_pair() {
+        let hole = parse_cards("Ah Kd").unwrap();
+        let board = parse_cards("Ac 7s 2h 9c 4d").unwrap();
+        let threats = hands_that_beat(&hole, &board, 1);
+        let categories: Vec<RankCategory> = threats.iter().map(|threat| threat.category).collect();
+        assert_eq!(
+            categories,
+            vec![
+                RankCategory::Straight,
+                RankCategory::ThreeOfAKind,
+                RankCategory::TwoPair
+            ]
+        );
+        let our_rank = evaluate(&[hole.clone(), board.clone()].concat());
+        for threat in &threats {
+            let cards = [threat.example.to_vec(), board.clone()].concat();
+            assert!(evaluate(&cards) < our_rank);
+            assert_eq!(get_rank_category(evaluate(&cards)), threat.category);
+        }
+        // Every holding that beats us, counted directly
+        let dead: CardSet = hole.iter().chain(&board).collect();
+        let beating = dead
+            .complement()
+            .iter()
+            .collect::<Vec<Card>>()
+            .into_iter()
+            .tuple_combinations()
+            .filter(|&(a, b)| evaluate(&[vec![a, b], board.clone()].concat()) < our_rank)
+            .count();
+        assert_eq!(
+            threats.iter().map(|threat| threat.combos).sum::<usize>(),
+            beating
+        );
+
+        let royal = parse_cards("Qh Jh Th 2c 3d").unwrap();
+        assert!(hands_that_beat(&hole, &royal, 1)[0].category == RankCategory::StraightFlush);
+        assert!(hands_that_beat(&parse_cards("Ah Kh").unwrap(), &royal, 1).is_empty());
+    }
+}